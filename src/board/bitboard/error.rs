@@ -1,3 +1,5 @@
+/// An error returned when converting a [crate::board::Bitboard] into a single
+/// [crate::board::Square].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum IntoSquareError {
     /// The board is empty.
@@ -6,8 +8,8 @@ pub enum IntoSquareError {
     TooManySquares,
 }
 
-impl std::fmt::Display for IntoSquareError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for IntoSquareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let error_msg = match self {
             Self::EmptyBoard => "The board is empty",
             Self::TooManySquares => "The board contains more than one square",
@@ -16,4 +18,4 @@ impl std::fmt::Display for IntoSquareError {
     }
 }
 
-impl std::error::Error for IntoSquareError {}
+impl core::error::Error for IntoSquareError {}
@@ -1,4 +1,4 @@
-/// An [Iterator](std::iter::Iterator) of [Square](crate::board::Square) contained in a
+/// An [Iterator](core::iter::Iterator) of [Square](crate::board::Square) contained in a
 /// [Bitboard].
 use crate::board::Bitboard;
 
@@ -30,4 +30,4 @@ impl Iterator for BitboardIterator {
 
 impl ExactSizeIterator for BitboardIterator {}
 
-impl std::iter::FusedIterator for BitboardIterator {}
+impl core::iter::FusedIterator for BitboardIterator {}
@@ -1,8 +1,8 @@
-use super::{File, Rank, Square};
+use super::{Color, Direction, File, Rank, Square};
 use crate::utils::static_assert;
 
 mod error;
-use error::*;
+pub use error::*;
 mod iterator;
 use iterator::*;
 mod superset;
@@ -54,7 +54,38 @@ impl Bitboard {
     pub const LIGHT_SQUARES: Bitboard = Bitboard(0x55AA55AA55AA55AA);
 
     /// The dark [Square]s on a board, e.g: [Square::A1].
-    pub const DARK_SQUARES: Bitboard = Bitboard(0x55AA55AA55AA55AA);
+    pub const DARK_SQUARES: Bitboard = Bitboard(0xAA55AA55AA55AA55);
+
+    /// Create a [Bitboard] from its raw 64-bit value, see [Bitboard] for the square mapping.
+    #[inline(always)]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Create a [Bitboard] containing only the [Square] at the intersection of the given [File] and
+    /// [Rank].
+    #[inline(always)]
+    pub fn square(file: File, rank: Rank) -> Self {
+        Square::new(file, rank).into_bitboard()
+    }
+
+    /// Create a [Bitboard] containing all the [Square]s of the given [Rank].
+    #[inline(always)]
+    pub fn rank(rank: Rank) -> Self {
+        rank.into_bitboard()
+    }
+
+    /// Create a [Bitboard] containing all the [Square]s of the given [File].
+    #[inline(always)]
+    pub fn file(file: File) -> Self {
+        file.into_bitboard()
+    }
+
+    /// Return the raw 64-bit value of the [Bitboard], see [Bitboard] for the square mapping.
+    #[inline(always)]
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
 
     /// Count the number of pieces in the [Bitboard].
     #[inline(always)]
@@ -68,6 +99,24 @@ impl Bitboard {
         self == Self::EMPTY
     }
 
+    /// Return true if the given [Square] is part of the [Bitboard].
+    #[inline(always)]
+    pub fn contains(self, square: Square) -> bool {
+        !(self & square).is_empty()
+    }
+
+    /// Return true if all squares of the [Bitboard] are part of `other`.
+    #[inline(always)]
+    pub fn is_subset(self, other: Bitboard) -> bool {
+        (self - other).is_empty()
+    }
+
+    /// Return true if the [Bitboard] has no square in common with `other`.
+    #[inline(always)]
+    pub fn is_disjoint(self, other: Bitboard) -> bool {
+        (self & other).is_empty()
+    }
+
     /// Return true if there are more than piece in the [Bitboard]. This is faster than testing
     /// `board.count() > 1`.
     #[inline(always)]
@@ -81,6 +130,55 @@ impl Bitboard {
         Square::try_from_index(self.0.trailing_zeros() as usize)
     }
 
+    /// Return the [Square] with the lowest index from a board known to be non-empty, the same one
+    /// as [Bitboard::any_square]. The result is meaningless on an empty board, and it will panic
+    /// in debug-mode if it happens.
+    #[inline(always)]
+    pub fn first_square(self) -> Square {
+        debug_assert!(!self.is_empty());
+        // Masking keeps the index in bounds, avoiding any branch
+        Square::from_index(self.0.trailing_zeros() as usize & 63)
+    }
+
+    /// Return the [Square] with the highest index from a board known to be non-empty. The result
+    /// is meaningless on an empty board, and it will panic in debug-mode if it happens.
+    #[inline(always)]
+    pub fn last_square(self) -> Square {
+        debug_assert!(!self.is_empty());
+        // Masking keeps the index in bounds, avoiding any branch
+        Square::from_index(63_usize.wrapping_sub(self.0.leading_zeros() as usize) & 63)
+    }
+
+    /// Return all [Square]s that are strictly in front of the board's pieces, on the same file, as
+    /// seen from the given [Color]'s perspective.
+    #[inline(always)]
+    pub fn front_span(self, color: Color) -> Self {
+        color.forward_direction().slide_board(self)
+    }
+
+    /// Return all [Square]s that are strictly behind the board's pieces, on the same file, as seen
+    /// from the given [Color]'s perspective.
+    #[inline(always)]
+    pub fn rear_span(self, color: Color) -> Self {
+        color.backward_direction().slide_board(self)
+    }
+
+    /// Return all [Square]s that can be reached from the board's pieces by a sequence of king
+    /// steps, only going through [Square]s that are part of `passable`. The board's own squares
+    /// are always part of the result.
+    pub fn flood_fill(self, passable: Bitboard) -> Self {
+        let mut res = self;
+        loop {
+            let neighbours = Direction::iter_royalty()
+                .fold(Bitboard::EMPTY, |acc, dir| acc | dir.move_board(res));
+            let filled = res | (neighbours & passable);
+            if filled == res {
+                return res;
+            }
+            res = filled;
+        }
+    }
+
     /// Iterate over the power-set of a given [Bitboard], yielding each possible sub-set of
     /// [Square] that belong to the [Bitboard]. In other words, generate all set of [Square] that
     /// contain all, some, or none of the [Square] that are in the given [Bitboard].
@@ -89,10 +187,34 @@ impl Bitboard {
     pub fn iter_power_set(self) -> impl Iterator<Item = Self> {
         BitboardPowerSetIterator::new(self)
     }
+
+    /// Iterate over the power-set of a given [Bitboard], in the reverse order of
+    /// [Bitboard::iter_power_set]: starting from the full [Bitboard], and ending with the empty
+    /// one.
+    #[inline(always)]
+    pub fn iter_subsets_rev(self) -> impl Iterator<Item = Self> {
+        BitboardReversePowerSetIterator::new(self)
+    }
+
+    /// Compute the subset of `mask` which follows this one, using the Carry-Rippler trick: the
+    /// subsets are enumerated in increasing numerical order, starting from and wrapping around to
+    /// [Bitboard::EMPTY]. This [Bitboard] must be a subset of `mask`.
+    #[inline(always)]
+    pub const fn next_subset(self, mask: Bitboard) -> Self {
+        Self(self.0.wrapping_sub(mask.0) & mask.0)
+    }
+
+    /// Compute the subset of `mask` which precedes this one, the reverse of
+    /// [Bitboard::next_subset]: the subsets are enumerated in decreasing numerical order, wrapping
+    /// around from [Bitboard::EMPTY] to `mask`. This [Bitboard] must be a subset of `mask`.
+    #[inline(always)]
+    pub const fn previous_subset(self, mask: Bitboard) -> Self {
+        Self(self.0.wrapping_sub(1) & mask.0)
+    }
 }
 
 // Ensure zero-cost (at least size-wise) wrapping.
-static_assert!(std::mem::size_of::<Bitboard>() == std::mem::size_of::<u64>());
+static_assert!(core::mem::size_of::<Bitboard>() == core::mem::size_of::<u64>());
 
 impl Default for Bitboard {
     fn default() -> Self {
@@ -110,6 +232,25 @@ impl IntoIterator for Bitboard {
     }
 }
 
+/// Display the [Bitboard] as a grid, from the eighth rank down to the first one, with `X` for
+/// occupied squares and `.` for empty ones.
+impl core::fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for rank in (0..Rank::NUM_VARIANTS).rev().map(Rank::from_index) {
+            for file in File::iter() {
+                let c = if self.contains(Square::new(file, rank)) {
+                    'X'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 /// If the given [Bitboard] is a singleton piece on a board, return the [Square] that it is
 /// occupying. Otherwise return `None`.
 impl TryInto<Square> for Bitboard {
@@ -124,27 +265,37 @@ impl TryInto<Square> for Bitboard {
 }
 
 /// Treat bitboard as a set of squares, shift each square's index left by the amount given.
-impl std::ops::Shl<usize> for Bitboard {
+/// Squares shifted past the end of the board are dropped, shifting by 64 or more yields an empty
+/// board.
+impl core::ops::Shl<usize> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
     fn shl(self, rhs: usize) -> Self::Output {
+        if rhs >= 64 {
+            return Bitboard::EMPTY;
+        }
         Bitboard(self.0 << rhs)
     }
 }
 
 /// Treat bitboard as a set of squares, shift each square's index right by the amount given.
-impl std::ops::Shr<usize> for Bitboard {
+/// Squares shifted past the start of the board are dropped, shifting by 64 or more yields an empty
+/// board.
+impl core::ops::Shr<usize> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
     fn shr(self, rhs: usize) -> Self::Output {
+        if rhs >= 64 {
+            return Bitboard::EMPTY;
+        }
         Bitboard(self.0 >> rhs)
     }
 }
 
 /// Treat bitboard as a set of squares, shift each square's index left by the amount given.
-impl std::ops::ShlAssign<usize> for Bitboard {
+impl core::ops::ShlAssign<usize> for Bitboard {
     #[inline(always)]
     fn shl_assign(&mut self, rhs: usize) {
         *self = *self << rhs;
@@ -152,7 +303,7 @@ impl std::ops::ShlAssign<usize> for Bitboard {
 }
 
 /// Treat bitboard as a set of squares, shift each square's index right by the amount given.
-impl std::ops::ShrAssign<usize> for Bitboard {
+impl core::ops::ShrAssign<usize> for Bitboard {
     #[inline(always)]
     fn shr_assign(&mut self, rhs: usize) {
         *self = *self >> rhs;
@@ -160,7 +311,7 @@ impl std::ops::ShrAssign<usize> for Bitboard {
 }
 
 /// Treat bitboard as a set of squares, and invert the set.
-impl std::ops::Not for Bitboard {
+impl core::ops::Not for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -170,7 +321,7 @@ impl std::ops::Not for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in either sets.
-impl std::ops::BitOr<Bitboard> for Bitboard {
+impl core::ops::BitOr<Bitboard> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -180,7 +331,7 @@ impl std::ops::BitOr<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitOr<Square> for Bitboard {
+impl core::ops::BitOr<Square> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -190,7 +341,7 @@ impl std::ops::BitOr<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in either sets.
-impl std::ops::BitOrAssign<Bitboard> for Bitboard {
+impl core::ops::BitOrAssign<Bitboard> for Bitboard {
     #[inline(always)]
     fn bitor_assign(&mut self, rhs: Bitboard) {
         *self = *self | rhs;
@@ -198,7 +349,7 @@ impl std::ops::BitOrAssign<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitOrAssign<Square> for Bitboard {
+impl core::ops::BitOrAssign<Square> for Bitboard {
     #[inline(always)]
     fn bitor_assign(&mut self, rhs: Square) {
         *self = *self | rhs;
@@ -206,7 +357,7 @@ impl std::ops::BitOrAssign<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in both sets.
-impl std::ops::BitAnd<Bitboard> for Bitboard {
+impl core::ops::BitAnd<Bitboard> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -216,7 +367,7 @@ impl std::ops::BitAnd<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitAnd<Square> for Bitboard {
+impl core::ops::BitAnd<Square> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -226,7 +377,7 @@ impl std::ops::BitAnd<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in both sets.
-impl std::ops::BitAndAssign<Bitboard> for Bitboard {
+impl core::ops::BitAndAssign<Bitboard> for Bitboard {
     #[inline(always)]
     fn bitand_assign(&mut self, rhs: Bitboard) {
         *self = *self & rhs;
@@ -234,7 +385,7 @@ impl std::ops::BitAndAssign<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitAndAssign<Square> for Bitboard {
+impl core::ops::BitAndAssign<Square> for Bitboard {
     #[inline(always)]
     fn bitand_assign(&mut self, rhs: Square) {
         *self = *self & rhs;
@@ -242,7 +393,7 @@ impl std::ops::BitAndAssign<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in exactly one of either set.
-impl std::ops::BitXor<Bitboard> for Bitboard {
+impl core::ops::BitXor<Bitboard> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -252,7 +403,7 @@ impl std::ops::BitXor<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitXor<Square> for Bitboard {
+impl core::ops::BitXor<Square> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -262,7 +413,7 @@ impl std::ops::BitXor<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, keep squares that are in exactly one of either set.
-impl std::ops::BitXorAssign<Bitboard> for Bitboard {
+impl core::ops::BitXorAssign<Bitboard> for Bitboard {
     #[inline(always)]
     fn bitxor_assign(&mut self, rhs: Bitboard) {
         *self = *self ^ rhs;
@@ -270,7 +421,7 @@ impl std::ops::BitXorAssign<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::BitXorAssign<Square> for Bitboard {
+impl core::ops::BitXorAssign<Square> for Bitboard {
     #[inline(always)]
     fn bitxor_assign(&mut self, rhs: Square) {
         *self = *self ^ rhs;
@@ -278,7 +429,7 @@ impl std::ops::BitXorAssign<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, and substract one set from another.
-impl std::ops::Sub<Bitboard> for Bitboard {
+impl core::ops::Sub<Bitboard> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -288,7 +439,7 @@ impl std::ops::Sub<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::Sub<Square> for Bitboard {
+impl core::ops::Sub<Square> for Bitboard {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -298,7 +449,7 @@ impl std::ops::Sub<Square> for Bitboard {
 }
 
 /// Treat each bitboard as a set of squares, and substract one set from another.
-impl std::ops::SubAssign<Bitboard> for Bitboard {
+impl core::ops::SubAssign<Bitboard> for Bitboard {
     #[inline(always)]
     fn sub_assign(&mut self, rhs: Bitboard) {
         *self = *self - rhs;
@@ -306,7 +457,7 @@ impl std::ops::SubAssign<Bitboard> for Bitboard {
 }
 
 /// Treat the [Square] as a singleton bitboard, and apply the operator.
-impl std::ops::SubAssign<Square> for Bitboard {
+impl core::ops::SubAssign<Square> for Bitboard {
     #[inline(always)]
     fn sub_assign(&mut self, rhs: Square) {
         *self = *self - rhs;
@@ -318,7 +469,7 @@ mod test {
     use std::collections::HashSet;
 
     use super::*;
-    use crate::board::{square::*, File, Rank};
+    use crate::board::{square::*, Color, File, Rank};
 
     #[test]
     fn count() {
@@ -327,6 +478,40 @@ mod test {
         assert_eq!(Bitboard::ALL.count(), 64);
     }
 
+    #[test]
+    fn constructors() {
+        assert_eq!(Bitboard::file(File::A), Bitboard::FILES[0]);
+        assert_eq!(Bitboard::file(File::H), Bitboard::FILES[7]);
+        assert_eq!(Bitboard::rank(Rank::First), Bitboard::RANKS[0]);
+        assert_eq!(Bitboard::rank(Rank::Eighth), Bitboard::RANKS[7]);
+        assert_eq!(
+            Bitboard::square(File::E, Rank::Fourth),
+            Bitboard::file(File::E) & Bitboard::rank(Rank::Fourth)
+        );
+        assert_eq!(
+            Bitboard::square(File::E, Rank::Fourth),
+            Square::E4.into_bitboard()
+        );
+    }
+
+    #[test]
+    fn flood_fill() {
+        let a1 = Square::A1.into_bitboard();
+        assert_eq!(a1.flood_fill(Bitboard::ALL), Bitboard::ALL);
+        assert_eq!(a1.flood_fill(Bitboard::EMPTY), a1);
+        assert_eq!(Bitboard::EMPTY.flood_fill(Bitboard::ALL), Bitboard::EMPTY);
+        // A wall on the D file stops the fill
+        let wall = Bitboard::file(File::D);
+        let queen_side =
+            Bitboard::file(File::A) | Bitboard::file(File::B) | Bitboard::file(File::C);
+        assert_eq!(a1.flood_fill(!wall), queen_side);
+        // A single gap in the wall lets it through
+        assert_eq!(
+            a1.flood_fill(!(wall - Square::D5)),
+            Bitboard::ALL - (wall - Square::D5)
+        );
+    }
+
     #[test]
     fn iter() {
         assert_eq!(Bitboard::EMPTY.into_iter().collect::<Vec<_>>(), Vec::new());
@@ -362,12 +547,20 @@ mod test {
     fn left_shift() {
         assert_eq!(Bitboard::RANKS[0] << 1, Bitboard::RANKS[1]);
         assert_eq!(Bitboard::FILES[0] << 8, Bitboard::FILES[1]);
+        assert_eq!(Bitboard::ALL << 63, Square::H8.into_bitboard());
+        assert_eq!(Bitboard::ALL << 64, Bitboard::EMPTY);
+        assert_eq!(Bitboard::ALL << 100, Bitboard::EMPTY);
     }
 
     #[test]
     fn right_shift() {
         assert_eq!(Bitboard::RANKS[1] >> 1, Bitboard::RANKS[0]);
         assert_eq!(Bitboard::FILES[1] >> 8, Bitboard::FILES[0]);
+        assert_eq!(Bitboard::ALL >> 63, Square::A1.into_bitboard());
+        assert_eq!(Bitboard::ALL >> 64, Bitboard::EMPTY);
+        let mut board = Bitboard::ALL;
+        board >>= 64;
+        assert_eq!(board, Bitboard::EMPTY);
     }
 
     #[test]
@@ -402,6 +595,57 @@ mod test {
         assert_eq!(Bitboard::FILES[0] - Square::A1, Bitboard(0xff - 1));
     }
 
+    #[test]
+    fn bits_round_trip() {
+        for board in [
+            Bitboard::EMPTY,
+            Bitboard::ALL,
+            Bitboard::DIAGONAL,
+            Bitboard::ANTI_DIAGONAL,
+            Bitboard::LIGHT_SQUARES,
+            Bitboard::DARK_SQUARES,
+        ] {
+            assert_eq!(Bitboard::from_bits(board.bits()), board);
+        }
+        assert_eq!(Square::A1.into_bitboard().bits(), 1);
+        assert_eq!(Bitboard::from_bits(1 << 63), Square::H8.into_bitboard());
+    }
+
+    #[test]
+    fn square_colors() {
+        assert_eq!(
+            Bitboard::LIGHT_SQUARES | Bitboard::DARK_SQUARES,
+            Bitboard::ALL
+        );
+        assert!(Bitboard::LIGHT_SQUARES.is_disjoint(Bitboard::DARK_SQUARES));
+        assert!(Bitboard::LIGHT_SQUARES.contains(Square::H1));
+        assert!(Bitboard::DARK_SQUARES.contains(Square::A1));
+    }
+
+    #[test]
+    fn contains() {
+        assert!(Bitboard::FILES[0].contains(Square::A1));
+        assert!(Bitboard::FILES[0].contains(Square::A8));
+        assert!(!Bitboard::FILES[0].contains(Square::B1));
+        assert!(!Bitboard::EMPTY.contains(Square::A1));
+        assert!(Bitboard::ALL.contains(Square::H8));
+    }
+
+    #[test]
+    fn subset_and_disjoint() {
+        let rank = Rank::First.into_bitboard();
+        let file = File::A.into_bitboard();
+        assert!(Bitboard::EMPTY.is_subset(rank));
+        assert!(rank.is_subset(Bitboard::ALL));
+        assert!(rank.is_subset(rank));
+        assert!(!rank.is_subset(file));
+        assert!((rank & file).is_subset(file));
+        assert!(!rank.is_disjoint(file));
+        assert!(rank.is_disjoint(Rank::Second.into_bitboard()));
+        assert!(file.is_disjoint(File::H.into_bitboard()));
+        assert!(Bitboard::EMPTY.is_disjoint(Bitboard::ALL));
+    }
+
     #[test]
     fn more_than_one() {
         assert!(!Bitboard::EMPTY.has_more_than_one());
@@ -412,6 +656,50 @@ mod test {
         assert!(Bitboard::ALL.has_more_than_one());
     }
 
+    #[test]
+    fn front_span() {
+        assert_eq!(
+            Square::E2.into_bitboard().front_span(Color::White),
+            File::E.into_bitboard() - Square::E1 - Square::E2
+        );
+        assert_eq!(
+            Square::E7.into_bitboard().front_span(Color::Black),
+            File::E.into_bitboard() - Square::E7 - Square::E8
+        );
+        assert_eq!(
+            (Square::A2 | Square::H5).front_span(Color::White),
+            Square::A3
+                | Square::A4
+                | Square::A5
+                | Square::A6
+                | Square::A7
+                | Square::A8
+                | Square::H6
+                | Square::H7
+                | Square::H8
+        );
+        assert_eq!(
+            Square::E8.into_bitboard().front_span(Color::White),
+            Bitboard::EMPTY
+        );
+    }
+
+    #[test]
+    fn rear_span() {
+        assert_eq!(
+            Square::E2.into_bitboard().rear_span(Color::White),
+            Square::E1.into_bitboard()
+        );
+        assert_eq!(
+            Square::E7.into_bitboard().rear_span(Color::Black),
+            Square::E8.into_bitboard()
+        );
+        assert_eq!(
+            Square::E1.into_bitboard().rear_span(Color::White),
+            Bitboard::EMPTY
+        );
+    }
+
     #[test]
     fn iter_power_set_empty() {
         assert_eq!(
@@ -483,6 +771,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn iter_subsets_rev() {
+        let mask = Square::A1 | Square::C4 | Square::H8;
+        let forward = mask.iter_power_set().collect::<Vec<_>>();
+        let mut backward = mask.iter_subsets_rev().collect::<Vec<_>>();
+        assert_eq!(forward.len(), 8);
+        assert_eq!(backward.first(), Some(&mask));
+        assert_eq!(backward.last(), Some(&Bitboard::EMPTY));
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(
+            Bitboard::EMPTY.iter_subsets_rev().collect::<Vec<_>>(),
+            vec![Bitboard::EMPTY]
+        );
+    }
+
+    #[test]
+    fn next_and_previous_subset() {
+        let mask = Square::A1 | Square::C4 | Square::H8;
+        for subset in mask.iter_power_set() {
+            assert!(subset.next_subset(mask).is_subset(mask));
+            assert_eq!(subset.next_subset(mask).previous_subset(mask), subset);
+        }
+        assert_eq!(mask.next_subset(mask), Bitboard::EMPTY);
+        assert_eq!(Bitboard::EMPTY.previous_subset(mask), mask);
+    }
+
     #[test]
     fn any_square() {
         for square in Square::iter() {
@@ -500,6 +815,31 @@ mod test {
         assert!(Bitboard::ALL.any_square().is_some());
     }
 
+    #[test]
+    fn first_and_last_square() {
+        for square in Square::iter() {
+            assert_eq!(square.into_bitboard().first_square(), square);
+            assert_eq!(square.into_bitboard().last_square(), square);
+        }
+        for board in [
+            Bitboard::ALL,
+            Bitboard::FILES[3],
+            Bitboard::RANKS[5],
+            Square::B3 | Square::G7,
+        ] {
+            assert_eq!(Some(board.first_square()), board.any_square());
+            assert_eq!(Some(board.last_square()), board.into_iter().last());
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            (Square::A1 | Square::H8 | Square::E4).to_string(),
+            ".......X\n........\n........\n........\n....X...\n........\n........\nX.......\n"
+        );
+    }
+
     #[test]
     fn into_square() {
         for square in Square::iter() {
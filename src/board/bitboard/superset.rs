@@ -30,7 +30,7 @@ impl Iterator for BitboardPowerSetIterator {
             return None;
         }
         let res = self.subset;
-        self.subset = Bitboard(self.subset.0.wrapping_sub(self.board.0)) & self.board;
+        self.subset = self.subset.next_subset(self.board);
         self.done = self.subset.is_empty();
         Some(res)
     }
@@ -43,4 +43,48 @@ impl Iterator for BitboardPowerSetIterator {
 
 impl ExactSizeIterator for BitboardPowerSetIterator {}
 
-impl std::iter::FusedIterator for BitboardPowerSetIterator {}
+impl core::iter::FusedIterator for BitboardPowerSetIterator {}
+
+/// Iterator over a [Bitboard] mask, which yields all potential subsets of the given board, in the
+/// reverse order of [BitboardPowerSetIterator].
+pub struct BitboardReversePowerSetIterator {
+    /// The starting board.
+    board: Bitboard,
+    /// The next subset.
+    subset: Bitboard,
+    /// Whether or not iteration is done.
+    done: bool,
+}
+
+impl BitboardReversePowerSetIterator {
+    pub fn new(board: Bitboard) -> Self {
+        Self {
+            board,
+            subset: board,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for BitboardReversePowerSetIterator {
+    type Item = Bitboard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.subset;
+        self.done = self.subset.is_empty();
+        self.subset = self.subset.previous_subset(self.board);
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = 1 << self.board.count();
+        (size, Some(size))
+    }
+}
+
+impl ExactSizeIterator for BitboardReversePowerSetIterator {}
+
+impl core::iter::FusedIterator for BitboardReversePowerSetIterator {}
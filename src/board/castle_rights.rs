@@ -1,4 +1,4 @@
-use super::{Bitboard, Color, File, Square};
+use super::{Bitboard, CastlingFiles, Color, Piece, Square};
 
 /// Current castle rights for a player.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -57,7 +57,7 @@ impl CastleRights {
     /// This should only be called with values that can be output by [CastleRights::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [CastleRights].
@@ -116,13 +116,48 @@ impl CastleRights {
         unsafe { Self::from_index_unchecked(self.index() & !to_remove.index()) }
     }
 
-    /// Which rooks have not been moved for a given [CastleRights] and [Color].
+    /// Compute the [CastleRights] of the given [Color] left after moving one of its [Piece] from
+    /// the given [Square], or having it captured there. Moving the king loses all rights, moving
+    /// or losing one of the castling rooks given by [CastlingFiles] loses the rights on that side.
+    pub fn after_move(
+        self,
+        color: Color,
+        files: CastlingFiles,
+        piece: Piece,
+        square: Square,
+    ) -> Self {
+        match (piece, square.file()) {
+            // Only the rooks in their original squares matter
+            (Piece::Rook, _) if square.rank() != color.first_rank() => self,
+            (Piece::Rook, file) if file == files.queen_side() => self.without_queen_side(),
+            (Piece::Rook, file) if file == files.king_side() => self.without_king_side(),
+            (Piece::King, _) => Self::NoSide,
+            _ => self,
+        }
+    }
+
+    /// Return the castling rights segment of a FEN string for the given [Color], e.g: `"KQ"` for
+    /// white or `"k"` for black. Returns an empty string for [CastleRights::NoSide].
+    pub fn to_fen(self, color: Color) -> &'static str {
+        match (color, self) {
+            (_, Self::NoSide) => "",
+            (Color::White, Self::KingSide) => "K",
+            (Color::White, Self::QueenSide) => "Q",
+            (Color::White, Self::BothSides) => "KQ",
+            (Color::Black, Self::KingSide) => "k",
+            (Color::Black, Self::QueenSide) => "q",
+            (Color::Black, Self::BothSides) => "kq",
+        }
+    }
+
+    /// Which rooks have not been moved for a given [CastleRights] and [Color], castling with the
+    /// rooks on the given [CastlingFiles].
     #[inline(always)]
-    pub fn unmoved_rooks(self, color: Color) -> Bitboard {
+    pub fn unmoved_rooks(self, color: Color, files: CastlingFiles) -> Bitboard {
         let rank = color.first_rank();
 
-        let king_side_square = Square::new(File::H, rank);
-        let queen_side_square = Square::new(File::A, rank);
+        let king_side_square = Square::new(files.king_side(), rank);
+        let queen_side_square = Square::new(files.queen_side(), rank);
 
         match self {
             Self::NoSide => Bitboard::EMPTY,
@@ -136,6 +171,7 @@ impl CastleRights {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::board::File;
 
     #[test]
     fn from_index() {
@@ -209,39 +245,118 @@ mod test {
         );
     }
 
+    #[test]
+    fn to_fen() {
+        for color in Color::iter() {
+            assert_eq!(CastleRights::NoSide.to_fen(color), "");
+        }
+        assert_eq!(CastleRights::KingSide.to_fen(Color::White), "K");
+        assert_eq!(CastleRights::QueenSide.to_fen(Color::White), "Q");
+        assert_eq!(CastleRights::BothSides.to_fen(Color::White), "KQ");
+        assert_eq!(CastleRights::KingSide.to_fen(Color::Black), "k");
+        assert_eq!(CastleRights::QueenSide.to_fen(Color::Black), "q");
+        assert_eq!(CastleRights::BothSides.to_fen(Color::Black), "kq");
+    }
+
     #[test]
     fn unmoved_rooks() {
         assert_eq!(
-            CastleRights::NoSide.unmoved_rooks(Color::White),
+            CastleRights::NoSide.unmoved_rooks(Color::White, CastlingFiles::STANDARD),
             Bitboard::EMPTY
         );
         assert_eq!(
-            CastleRights::NoSide.unmoved_rooks(Color::Black),
+            CastleRights::NoSide.unmoved_rooks(Color::Black, CastlingFiles::STANDARD),
             Bitboard::EMPTY
         );
         assert_eq!(
-            CastleRights::KingSide.unmoved_rooks(Color::White),
+            CastleRights::KingSide.unmoved_rooks(Color::White, CastlingFiles::STANDARD),
             Square::H1.into_bitboard()
         );
         assert_eq!(
-            CastleRights::KingSide.unmoved_rooks(Color::Black),
+            CastleRights::KingSide.unmoved_rooks(Color::Black, CastlingFiles::STANDARD),
             Square::H8.into_bitboard()
         );
         assert_eq!(
-            CastleRights::QueenSide.unmoved_rooks(Color::White),
+            CastleRights::QueenSide.unmoved_rooks(Color::White, CastlingFiles::STANDARD),
             Square::A1.into_bitboard()
         );
         assert_eq!(
-            CastleRights::QueenSide.unmoved_rooks(Color::Black),
+            CastleRights::QueenSide.unmoved_rooks(Color::Black, CastlingFiles::STANDARD),
             Square::A8.into_bitboard()
         );
         assert_eq!(
-            CastleRights::BothSides.unmoved_rooks(Color::White),
+            CastleRights::BothSides.unmoved_rooks(Color::White, CastlingFiles::STANDARD),
             Square::A1 | Square::H1
         );
         assert_eq!(
-            CastleRights::BothSides.unmoved_rooks(Color::Black),
+            CastleRights::BothSides.unmoved_rooks(Color::Black, CastlingFiles::STANDARD),
             Square::A8 | Square::H8
         );
+        assert_eq!(
+            CastleRights::BothSides
+                .unmoved_rooks(Color::White, CastlingFiles::new(File::G, File::B)),
+            Square::B1 | Square::G1
+        );
+    }
+
+    #[test]
+    fn after_move() {
+        let rights = CastleRights::BothSides;
+        assert_eq!(
+            rights.after_move(
+                Color::White,
+                CastlingFiles::STANDARD,
+                Piece::Rook,
+                Square::A1
+            ),
+            CastleRights::KingSide
+        );
+        assert_eq!(
+            rights.after_move(
+                Color::Black,
+                CastlingFiles::STANDARD,
+                Piece::Rook,
+                Square::H8
+            ),
+            CastleRights::QueenSide
+        );
+        assert_eq!(
+            rights.after_move(
+                Color::White,
+                CastlingFiles::STANDARD,
+                Piece::King,
+                Square::E1
+            ),
+            CastleRights::NoSide
+        );
+        // Not in its original corner
+        assert_eq!(
+            rights.after_move(
+                Color::White,
+                CastlingFiles::STANDARD,
+                Piece::Rook,
+                Square::A8
+            ),
+            CastleRights::BothSides
+        );
+        assert_eq!(
+            rights.after_move(
+                Color::White,
+                CastlingFiles::STANDARD,
+                Piece::Queen,
+                Square::A1
+            ),
+            CastleRights::BothSides
+        );
+        // Only the castling rooks matter
+        let files = CastlingFiles::new(File::H, File::B);
+        assert_eq!(
+            rights.after_move(Color::White, files, Piece::Rook, Square::A1),
+            CastleRights::BothSides
+        );
+        assert_eq!(
+            rights.after_move(Color::White, files, Piece::Rook, Square::B1),
+            CastleRights::KingSide
+        );
     }
 }
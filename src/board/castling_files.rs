@@ -0,0 +1,155 @@
+use super::{CastleRights, File};
+
+/// The [File]s of the rooks a player castles with, on either side of its king, along with the
+/// starting [File] of the king. In standard chess, those are [File::H] on the king side and
+/// [File::A] on the queen side with the king on [File::E], other placements can be named by X-FEN
+/// or Shredder-FEN castling rights, e.g: in Chess960 positions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CastlingFiles {
+    king: File,
+    king_side: File,
+    queen_side: File,
+}
+
+impl CastlingFiles {
+    /// The castling rooks of standard chess, in their original corners.
+    pub const STANDARD: Self = Self {
+        king: File::E,
+        king_side: File::H,
+        queen_side: File::A,
+    };
+
+    /// Create a [CastlingFiles] from the [File] of the king-side and queen-side rooks, with the
+    /// king on [File::E], see [CastlingFiles::with_king] otherwise.
+    #[inline(always)]
+    pub fn new(king_side: File, queen_side: File) -> Self {
+        Self {
+            king: File::E,
+            king_side,
+            queen_side,
+        }
+    }
+
+    /// The [File] the king castles from.
+    #[inline(always)]
+    pub fn king(self) -> File {
+        self.king
+    }
+
+    /// The [File] of the rook used to castle king-side.
+    #[inline(always)]
+    pub fn king_side(self) -> File {
+        self.king_side
+    }
+
+    /// The [File] of the rook used to castle queen-side.
+    #[inline(always)]
+    pub fn queen_side(self) -> File {
+        self.queen_side
+    }
+
+    /// Castle with the king starting on the given [File].
+    #[inline(always)]
+    pub fn with_king(self, file: File) -> Self {
+        Self { king: file, ..self }
+    }
+
+    /// Use the rook on the given [File] to castle king-side.
+    #[inline(always)]
+    pub fn with_king_side(self, file: File) -> Self {
+        Self {
+            king_side: file,
+            ..self
+        }
+    }
+
+    /// Use the rook on the given [File] to castle queen-side.
+    #[inline(always)]
+    pub fn with_queen_side(self, file: File) -> Self {
+        Self {
+            queen_side: file,
+            ..self
+        }
+    }
+
+    /// Reset the [File] of the sides which cannot castle with the given [CastleRights] to their
+    /// standard value, along with the king's if neither side can, so that positions which only
+    /// differ by an unusable rook compare equal.
+    pub fn for_rights(self, rights: CastleRights) -> Self {
+        Self {
+            king: if rights == CastleRights::NoSide {
+                Self::STANDARD.king
+            } else {
+                self.king
+            },
+            king_side: if rights.has_king_side() {
+                self.king_side
+            } else {
+                Self::STANDARD.king_side
+            },
+            queen_side: if rights.has_queen_side() {
+                self.queen_side
+            } else {
+                Self::STANDARD.queen_side
+            },
+        }
+    }
+}
+
+/// Use the standard castling rooks as a default value.
+impl Default for CastlingFiles {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            CastlingFiles::default(),
+            CastlingFiles::new(File::H, File::A)
+        );
+    }
+
+    #[test]
+    fn with_side() {
+        let files = CastlingFiles::STANDARD.with_queen_side(File::B);
+        assert_eq!(files.king_side(), File::H);
+        assert_eq!(files.queen_side(), File::B);
+        let files = files.with_king_side(File::G);
+        assert_eq!(files, CastlingFiles::new(File::G, File::B));
+        assert_eq!(files.king(), File::E);
+        assert_eq!(files.with_king(File::F).king(), File::F);
+    }
+
+    #[test]
+    fn for_rights() {
+        let files = CastlingFiles::new(File::G, File::B);
+        assert_eq!(files.for_rights(CastleRights::BothSides), files);
+        assert_eq!(
+            files.for_rights(CastleRights::KingSide),
+            CastlingFiles::new(File::G, File::A)
+        );
+        assert_eq!(
+            files.for_rights(CastleRights::QueenSide),
+            CastlingFiles::new(File::H, File::B)
+        );
+        assert_eq!(
+            files.for_rights(CastleRights::NoSide),
+            CastlingFiles::STANDARD
+        );
+        let files = files.with_king(File::F);
+        assert_eq!(
+            files.for_rights(CastleRights::KingSide),
+            CastlingFiles::new(File::G, File::A).with_king(File::F)
+        );
+        assert_eq!(
+            files.for_rights(CastleRights::NoSide),
+            CastlingFiles::STANDARD
+        );
+    }
+}
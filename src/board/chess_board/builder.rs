@@ -1,4 +1,6 @@
-use crate::board::{Bitboard, CastleRights, ChessBoard, Color, Piece, Square, ValidationError};
+use crate::board::{
+    Bitboard, CastleRights, CastlingFiles, ChessBoard, Color, Piece, Square, ValidationError,
+};
 
 /// Build a [ChessBoard] one piece at a time.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -7,6 +9,7 @@ pub struct ChessBoardBuilder {
     pieces: [Option<(Piece, Color)>; Square::NUM_VARIANTS],
     // Same fields as [ChessBoard].
     castle_rights: [CastleRights; Color::NUM_VARIANTS],
+    castling_files: [CastlingFiles; Color::NUM_VARIANTS],
     en_passant: Option<Square>,
     half_move_clock: u32,
     side: Color,
@@ -19,6 +22,7 @@ impl ChessBoardBuilder {
         Self {
             pieces: [None; Square::NUM_VARIANTS],
             castle_rights: [CastleRights::NoSide; Color::NUM_VARIANTS],
+            castling_files: [CastlingFiles::STANDARD; Color::NUM_VARIANTS],
             en_passant: Default::default(),
             half_move_clock: Default::default(),
             side: Color::White,
@@ -31,6 +35,11 @@ impl ChessBoardBuilder {
         self
     }
 
+    pub fn with_castling_files(&mut self, files: CastlingFiles, color: Color) -> &mut Self {
+        self.castling_files[color.index()] = files;
+        self
+    }
+
     pub fn with_en_passant(&mut self, square: Square) -> &mut Self {
         self.en_passant = Some(square);
         self
@@ -64,7 +73,7 @@ impl Default for ChessBoardBuilder {
 }
 
 /// Index a [ChessBoardBuilder] with a [Square] to access its pieces.
-impl std::ops::Index<Square> for ChessBoardBuilder {
+impl core::ops::Index<Square> for ChessBoardBuilder {
     type Output = Option<(Piece, Color)>;
 
     fn index(&self, square: Square) -> &Self::Output {
@@ -73,7 +82,7 @@ impl std::ops::Index<Square> for ChessBoardBuilder {
 }
 
 /// Index a [ChessBoardBuilder] with a [Square] to access its pieces.
-impl std::ops::IndexMut<Square> for ChessBoardBuilder {
+impl core::ops::IndexMut<Square> for ChessBoardBuilder {
     fn index_mut(&mut self, square: Square) -> &mut Self::Output {
         &mut self.pieces[square.index()]
     }
@@ -89,6 +98,7 @@ impl TryFrom<ChessBoardBuilder> for ChessBoard {
         let ChessBoardBuilder {
             pieces,
             castle_rights,
+            castling_files,
             en_passant,
             half_move_clock,
             side,
@@ -106,18 +116,24 @@ impl TryFrom<ChessBoardBuilder> for ChessBoard {
 
         let total_plies = (turn_count - 1) * 2 + if side == Color::White { 0 } else { 1 };
 
-        let board = ChessBoard {
+        let mut board = ChessBoard {
             piece_occupancy,
             color_occupancy,
             combined_occupancy,
             castle_rights,
+            castling_files,
             en_passant,
             half_move_clock,
             total_plies,
             side,
+            hash: 0,
+            checkers: Bitboard::EMPTY,
         };
+        board.hash = board.zobrist_from_scratch();
 
         board.validate()?;
+        // Only done once validated, as it needs exactly one king
+        board.checkers = board.compute_checkers(board.current_player());
         Ok(board)
     }
 }
@@ -139,6 +155,7 @@ mod test {
 
         for color in Color::iter() {
             builder.with_castle_rights(board.castle_rights(color), color);
+            builder.with_castling_files(board.castling_files(color), color);
         }
 
         if let Some(square) = board.en_passant() {
@@ -0,0 +1,134 @@
+use super::ChessBoard;
+use crate::board::{Bitboard, Piece, Square};
+use crate::movegen;
+
+/// Information about the checks and pins on the current player's king, see
+/// [ChessBoard::check_info].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckInfo {
+    /// The opponent's pieces giving check.
+    pub checkers: Bitboard,
+    /// The current player's pieces which are pinned to their king.
+    pub pinned: Bitboard,
+    /// The opponent's sliding pieces pinning a piece to the king.
+    pub pinners: Bitboard,
+    /// The squares a piece other than the king may move to: [Bitboard::ALL] when not in check,
+    /// the checker and the squares between it and the king when in single check, and
+    /// [Bitboard::EMPTY] when in double check.
+    pub check_mask: Bitboard,
+}
+
+/// Return the squares strictly between two [Square]s, if they share a rank, file, or diagonal.
+/// Otherwise return [Bitboard::EMPTY].
+fn squares_between(lhs: Square, rhs: Square) -> Bitboard {
+    let (lhs_board, rhs_board) = (lhs.into_bitboard(), rhs.into_bitboard());
+    if movegen::rook_moves(lhs, Bitboard::EMPTY).contains(rhs) {
+        movegen::rook_moves(lhs, rhs_board) & movegen::rook_moves(rhs, lhs_board)
+    } else if movegen::bishop_moves(lhs, Bitboard::EMPTY).contains(rhs) {
+        movegen::bishop_moves(lhs, rhs_board) & movegen::bishop_moves(rhs, lhs_board)
+    } else {
+        Bitboard::EMPTY
+    }
+}
+
+impl ChessBoard {
+    /// Compute the checkers, pins, and check mask for the current player in a single pass, to
+    /// avoid re-computing them for each generated move.
+    pub fn check_info(&self) -> CheckInfo {
+        let color = self.current_player();
+        // Unwrap is fine, there should always be exactly one king per color
+        let king: Square = self.occupancy(Piece::King, color).try_into().unwrap();
+        let ours = self.color_occupancy(color);
+        let theirs = self.color_occupancy(!color);
+
+        let checkers = self.checkers();
+
+        // Look through our own pieces to find the sliders which would attack the king
+        let rooks = self.orthogonal_sliders(!color) & movegen::rook_moves(king, theirs);
+        let bishops = self.diagonal_sliders(!color) & movegen::bishop_moves(king, theirs);
+        let snipers = rooks | bishops;
+        let mut pinned = Bitboard::EMPTY;
+        let mut pinners = Bitboard::EMPTY;
+        for sniper in snipers {
+            let blockers = squares_between(king, sniper) & self.combined_occupancy();
+            if blockers.count() == 1 && blockers.is_subset(ours) {
+                pinned |= blockers;
+                pinners |= sniper;
+            }
+        }
+
+        let check_mask = match checkers.count() {
+            0 => Bitboard::ALL,
+            // Unwrap is fine, there is exactly one checker
+            1 => checkers | squares_between(king, checkers.any_square().unwrap()),
+            _ => Bitboard::EMPTY,
+        };
+
+        CheckInfo {
+            checkers,
+            pinned,
+            pinners,
+            check_mask,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn no_check() {
+        let info = ChessBoard::default().check_info();
+        assert_eq!(
+            info,
+            CheckInfo {
+                checkers: Bitboard::EMPTY,
+                pinned: Bitboard::EMPTY,
+                pinners: Bitboard::EMPTY,
+                check_mask: Bitboard::ALL,
+            }
+        );
+    }
+
+    #[test]
+    fn single_check() {
+        let position = ChessBoard::from_fen("4k3/8/8/b7/8/8/8/4K3 w - - 0 1").unwrap();
+        let info = position.check_info();
+        assert_eq!(info.checkers, Square::A5.into_bitboard());
+        assert_eq!(
+            info.check_mask,
+            Square::A5 | Square::B4 | Square::C3 | Square::D2
+        );
+
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+        let info = position.check_info();
+        assert_eq!(
+            info.check_mask,
+            Square::A1 | Square::B1 | Square::C1 | Square::D1
+        );
+
+        // A knight check cannot be blocked
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(position.check_info().check_mask, Square::D3.into_bitboard());
+    }
+
+    #[test]
+    fn double_check() {
+        let position = ChessBoard::from_fen("4k3/8/8/b7/4r3/8/8/4K3 w - - 0 1").unwrap();
+        let info = position.check_info();
+        assert_eq!(info.checkers, Square::A5 | Square::E4);
+        assert_eq!(info.check_mask, Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn pins() {
+        let position = ChessBoard::from_fen("4k3/4r3/8/b7/4q3/2N5/4B3/4K3 w - - 0 1").unwrap();
+        let info = position.check_info();
+        assert_eq!(info.checkers, Bitboard::EMPTY);
+        // The rook behind the queen is not a pinner
+        assert_eq!(info.pinned, Square::C3 | Square::E2);
+        assert_eq!(info.pinners, Square::A5 | Square::E4);
+    }
+}
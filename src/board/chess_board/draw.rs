@@ -0,0 +1,184 @@
+use super::{ChessBoard, Terminal};
+use crate::board::{Bitboard, Color, History, Piece};
+
+/// The kind of draw caused by the material left on the board, see [ChessBoard::material_draw].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaterialDraw {
+    /// Neither side can checkmate, whatever the moves played, e.g: K vs K or KB vs K.
+    Dead,
+    /// A checkmate is possible, but cannot be forced by either side, e.g: KNN vs K or KN vs KN.
+    CannotForceMate,
+}
+
+impl ChessBoard {
+    /// Return the number of plies left before the fifty-move rule can be claimed, i.e: 100 minus
+    /// the [ChessBoard::half_move_clock], saturating at 0.
+    #[inline(always)]
+    pub fn plies_until_fifty_move_draw(&self) -> u32 {
+        100u32.saturating_sub(self.half_move_clock())
+    }
+
+    /// Return true if the game is drawn in the current position: by stalemate, by the fifty-move
+    /// rule unless the last move was checkmate, or because neither side can checkmate anymore,
+    /// see [MaterialDraw::Dead]. Threefold repetition requires the [History] of the positions
+    /// which were left to reach the current one, and is only detected if it is given.
+    pub fn is_draw(&self, history: Option<&History>) -> bool {
+        let terminal = self.terminal_state();
+        if terminal == Some(Terminal::Checkmate) {
+            return false;
+        }
+        terminal == Some(Terminal::Stalemate)
+            || self.half_move_clock() >= 100
+            || self.material_draw() == Some(MaterialDraw::Dead)
+            || history.is_some_and(|history| history.repetitions(self.zobrist_hash()) >= 2)
+    }
+
+    /// Classify the material left on the board, returning `None` if either side could still
+    /// force a checkmate.
+    pub fn material_draw(&self) -> Option<MaterialDraw> {
+        let heavy = self.piece_occupancy(Piece::Queen)
+            | self.piece_occupancy(Piece::Rook)
+            | self.piece_occupancy(Piece::Pawn);
+        if !heavy.is_empty() {
+            return None;
+        }
+
+        let bishops = self.piece_occupancy(Piece::Bishop);
+        let minors = bishops | self.piece_occupancy(Piece::Knight);
+
+        // A single minor piece, or bishops all on the same square color, cannot mate.
+        let same_color_bishops = bishops.is_disjoint(Bitboard::LIGHT_SQUARES)
+            || bishops.is_disjoint(Bitboard::DARK_SQUARES);
+        if minors.count() <= 1 || (minors == bishops && same_color_bishops) {
+            return Some(MaterialDraw::Dead);
+        }
+
+        // Any side with a bishop pair or bishop and knight can force mate on a lone king, two
+        // knights or a single minor piece cannot.
+        let cannot_force_mate = |color: Color| {
+            let own_minors = minors & self.color_occupancy(color);
+            own_minors.count() <= 1 || (own_minors.count() == 2 && own_minors.is_disjoint(bishops))
+        };
+        if Color::iter().all(cannot_force_mate) {
+            Some(MaterialDraw::CannotForceMate)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{Move, Square};
+    use crate::fen::FromFen;
+
+    #[test]
+    fn plies_until_fifty_move_draw() {
+        let position = ChessBoard::from_fen("4k3/4p3/8/8/8/8/4p3/R3K3 w - - 97 80").unwrap();
+        assert_eq!(position.plies_until_fifty_move_draw(), 3);
+        let position = position.play_move(Move::new(Square::A1, Square::A2, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 2);
+        let position = position.play_move(Move::new(Square::E8, Square::D8, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 1);
+        // A capture resets the clock
+        let position = position.play_move(Move::new(Square::E1, Square::E2, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 100);
+        let position = position.play_move(Move::new(Square::D8, Square::C8, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 99);
+        let position = position.play_move(Move::new(Square::A2, Square::A3, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 98);
+        // So does a pawn move
+        let position = position.play_move(Move::new(Square::E7, Square::E5, None));
+        assert_eq!(position.plies_until_fifty_move_draw(), 100);
+
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 120 80").unwrap();
+        assert_eq!(position.plies_until_fifty_move_draw(), 0);
+    }
+
+    #[test]
+    fn is_draw() {
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert!(!position.is_draw(None));
+        assert!(!ChessBoard::default().is_draw(Some(&History::new())));
+
+        // Stalemate
+        let position = ChessBoard::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert!(position.is_draw(None));
+        // Fifty-move rule, unless checkmated
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+        assert!(position.is_draw(None));
+        let position = ChessBoard::from_fen("4k3/4Q3/4K3/8/8/8/8/8 b - - 100 80").unwrap();
+        assert!(!position.is_draw(None));
+        // Insufficient material
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap();
+        assert!(position.is_draw(None));
+
+        // Threefold repetition
+        let mut position = ChessBoard::default();
+        let mut history = History::new();
+        for _ in 0..2 {
+            for chess_move in [
+                Move::new(Square::G1, Square::F3, None),
+                Move::new(Square::G8, Square::F6, None),
+                Move::new(Square::F3, Square::G1, None),
+                Move::new(Square::F6, Square::G8, None),
+            ] {
+                assert!(!position.is_draw(Some(&history)));
+                history.push(position.zobrist_hash());
+                position = position.play_move(chess_move);
+            }
+        }
+        assert!(position.is_draw(Some(&history)));
+        assert!(!position.is_draw(None));
+    }
+
+    #[test]
+    fn dead() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+            "4k3/8/8/8/8/8/8/2b1K3 w - - 0 1",
+            "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            assert_eq!(
+                position.material_draw(),
+                Some(MaterialDraw::Dead),
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn cannot_force_mate() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1",
+            "4kn2/8/8/8/8/8/8/4KN2 w - - 0 1",
+            "4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            assert_eq!(
+                position.material_draw(),
+                Some(MaterialDraw::CannotForceMate),
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn not_a_draw() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1",
+            "4k3/8/8/8/8/8/8/2B1KN2 w - - 0 1",
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            assert_eq!(position.material_draw(), None, "{}", fen);
+        }
+        assert_eq!(ChessBoard::default().material_draw(), None);
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::string::String;
+use core::fmt::Write as _;
+
+use super::ChessBoard;
+use crate::board::{Color, Piece};
+
+impl ChessBoard {
+    /// Return a human-readable dump of all the internal [crate::board::Bitboard]s and fields of
+    /// the board, to help with debugging a corrupted board.
+    pub fn dump_bitboards(&self) -> String {
+        let inner = || -> Result<String, core::fmt::Error> {
+            let mut res = String::new();
+
+            for piece in Piece::iter() {
+                writeln!(&mut res, "{:?}:\n{}", piece, self.piece_occupancy(piece))?;
+            }
+            for color in Color::iter() {
+                writeln!(&mut res, "{:?}:\n{}", color, self.color_occupancy(color))?;
+            }
+            writeln!(&mut res, "Combined:\n{}", self.combined_occupancy())?;
+
+            writeln!(&mut res, "Castle rights: {:?}", self.castle_rights)?;
+            writeln!(&mut res, "Castling files: {:?}", self.castling_files)?;
+            writeln!(&mut res, "En-passant: {:?}", self.en_passant())?;
+            writeln!(&mut res, "Half-move clock: {}", self.half_move_clock())?;
+            writeln!(&mut res, "Total plies: {}", self.total_plies())?;
+            writeln!(&mut res, "Side to move: {:?}", self.current_player())?;
+            writeln!(&mut res, "Hash: {:#018x}", self.hash)?;
+
+            Ok(res)
+        };
+
+        inner().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dump_bitboards() {
+        let dump = ChessBoard::default().dump_bitboards();
+        for piece in Piece::iter() {
+            assert!(dump.contains(&format!("{:?}:\n", piece)), "{:?}", piece);
+        }
+        for label in ["White:\n", "Black:\n", "Combined:\n", "Side to move: White"] {
+            assert!(dump.contains(label), "{}", label);
+        }
+        assert!(dump.contains("Combined:\nXXXXXXXX\nXXXXXXXX\n........\n"));
+    }
+}
@@ -0,0 +1,105 @@
+use super::{ChessBoard, EditError};
+use crate::board::{Color, Piece, Square};
+
+impl ChessBoard {
+    /// Put a [Piece] of the given [Color] on an empty [Square], e.g: in a position editor. Return
+    /// Err([EditError::SquareOccupied]) if a piece is already standing there, in which case the
+    /// board is left untouched. The resulting board might not be valid, see
+    /// [ChessBoard::validate].
+    pub fn add_piece(
+        &mut self,
+        square: Square,
+        piece: Piece,
+        color: Color,
+    ) -> Result<(), EditError> {
+        if self.combined_occupancy().contains(square) {
+            return Err(EditError::SquareOccupied);
+        }
+        self.edit(color, piece, square);
+        Ok(())
+    }
+
+    /// Remove the piece standing on the given [Square], e.g: in a position editor. Return
+    /// Err([EditError::SquareEmpty]) if there is none, in which case the board is left untouched.
+    /// The resulting board might not be valid, see [ChessBoard::validate].
+    pub fn remove_piece(&mut self, square: Square) -> Result<(), EditError> {
+        let piece = self.piece_on(square).ok_or(EditError::SquareEmpty)?;
+        // Unwrap is fine, the square is occupied
+        let color = self.color_on(square).unwrap();
+        self.edit(color, piece, square);
+        Ok(())
+    }
+
+    /// Toggle a piece on the board, updating the hash in case it changes whether the en-passant
+    /// target can be captured, and the cached checkers.
+    fn edit(&mut self, color: Color, piece: Piece, square: Square) {
+        self.hash ^= self.non_reversible_zobrist();
+        self.xor(color, piece, square);
+        self.hash ^= self.non_reversible_zobrist();
+        self.update_checkers();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Bitboard;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn add_piece() {
+        let mut position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        position
+            .add_piece(Square::D1, Piece::Queen, Color::White)
+            .unwrap();
+        position
+            .add_piece(Square::D8, Piece::Rook, Color::Black)
+            .unwrap();
+        let expected = ChessBoard::from_fen("3rk3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert_eq!(position, expected);
+        assert_eq!(position.zobrist_hash(), expected.zobrist_hash());
+
+        // Adding onto an occupied square leaves the board unchanged
+        assert_eq!(
+            position.add_piece(Square::D1, Piece::Knight, Color::Black),
+            Err(EditError::SquareOccupied)
+        );
+        assert_eq!(position, expected);
+
+        // The checkers are kept up to date
+        position
+            .add_piece(Square::E5, Piece::Rook, Color::Black)
+            .unwrap();
+        assert_eq!(position.checkers(), Square::E5.into_bitboard());
+        position.remove_piece(Square::E5).unwrap();
+        assert_eq!(position.checkers(), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn add_piece_en_passant_hash() {
+        let mut position = ChessBoard::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+        // Adding a pawn which can capture en-passant changes the hash
+        position
+            .add_piece(Square::D4, Piece::Pawn, Color::Black)
+            .unwrap();
+        let expected = ChessBoard::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(position.zobrist_hash(), expected.zobrist_hash());
+        assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+    }
+
+    #[test]
+    fn remove_piece() {
+        let mut position = ChessBoard::from_fen("3rk3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        position.remove_piece(Square::D1).unwrap();
+        position.remove_piece(Square::D8).unwrap();
+        let expected = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(position, expected);
+        assert_eq!(position.zobrist_hash(), expected.zobrist_hash());
+
+        assert_eq!(
+            position.remove_piece(Square::D1),
+            Err(EditError::SquareEmpty)
+        );
+        assert_eq!(position, expected);
+    }
+}
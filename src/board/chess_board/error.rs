@@ -1,3 +1,5 @@
+use crate::board::MoveParseError;
+
 /// A singular type for all errors that could happen during [crate::board::ChessBoard::is_valid].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ValidationError {
@@ -27,8 +29,8 @@ pub enum ValidationError {
     IncoherentPlieCount,
 }
 
-impl std::fmt::Display for ValidationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let error_msg = match self {
             Self::TooManyPieces => "too many pieces",
             Self::MissingKing => "missing king",
@@ -53,4 +55,193 @@ impl std::fmt::Display for ValidationError {
     }
 }
 
-impl std::error::Error for ValidationError {}
+impl core::error::Error for ValidationError {}
+
+/// A singular type for all errors that could happen when playing a checked [crate::board::Move],
+/// e.g: during [crate::board::ChessBoard::try_play_move].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoveError {
+    /// No piece of the current player on the start square.
+    NoPieceToMove,
+    /// Promotion on a move which does not bring a pawn to the last rank, or to an invalid piece.
+    InvalidPromotion,
+    /// A pawn reached the last rank without promoting.
+    MissingPromotion,
+    /// The move could not be parsed.
+    InvalidNotation(MoveParseError),
+    /// The move is not legal in the current position.
+    IllegalMove,
+}
+
+impl core::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error_msg = match self {
+            Self::NoPieceToMove => "no piece of the current player on the start square",
+            Self::InvalidPromotion => {
+                "promotion on a move which does not bring a pawn to the last rank, or to an invalid piece"
+            }
+            Self::MissingPromotion => "a pawn reached the last rank without promoting",
+            Self::InvalidNotation(err) => return write!(f, "invalid move notation: {}", err),
+            Self::IllegalMove => "the move is not legal in the current position",
+        };
+        write!(f, "{}", error_msg)
+    }
+}
+
+impl core::error::Error for MoveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidNotation(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Allow converting a [MoveParseError] into [MoveError], for use with the '?' operator.
+impl From<MoveParseError> for MoveError {
+    fn from(err: MoveParseError) -> Self {
+        Self::InvalidNotation(err)
+    }
+}
+
+/// A singular type for all errors that could happen when using Standard Algebraic Notation, e.g:
+/// during [crate::board::ChessBoard::parse_san].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SanError {
+    /// The input is not valid SAN.
+    ParseError,
+    /// No piece of the given type can reach the destination square.
+    NoSuchPiece,
+    /// The move would leave the king in check.
+    Illegal,
+    /// More than one legal move matches the input.
+    Ambiguous,
+}
+
+impl core::fmt::Display for SanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error_msg = match self {
+            Self::ParseError => "invalid SAN input",
+            Self::NoSuchPiece => "no piece of the given type can reach the destination square",
+            Self::Illegal => "the move would leave the king in check",
+            Self::Ambiguous => "more than one legal move matches the input",
+        };
+        write!(f, "{}", error_msg)
+    }
+}
+
+impl core::error::Error for SanError {}
+
+/// The reason for a [crate::board::Move] being rejected by
+/// [crate::board::ChessBoard::make_move_checked].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IllegalMove {
+    /// No piece of the current player on the start square.
+    NotYourPiece,
+    /// The destination square is occupied by one of the current player's pieces.
+    DestinationOccupied,
+    /// The piece cannot move to the destination square, whatever the blockers.
+    UnreachableSquare,
+    /// The piece could reach the destination square, if another piece was not in the way.
+    BlockedPath,
+    /// A pawn reached the last rank without promoting, promoted to an invalid piece, or promoted
+    /// without reaching the last rank.
+    InvalidPromotion,
+    /// The player is not allowed to castle on that side anymore.
+    NoCastlingRights,
+    /// The king is in check, or would cross an attacked square, while castling.
+    CastlingThroughCheck,
+    /// The move would leave the king in check.
+    WouldLeaveKingInCheck,
+}
+
+impl core::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error_msg = match self {
+            Self::NotYourPiece => "no piece of the current player on the start square",
+            Self::DestinationOccupied => "the destination square is occupied by an own piece",
+            Self::UnreachableSquare => "the piece cannot move to the destination square",
+            Self::BlockedPath => "another piece is in the way",
+            Self::InvalidPromotion => "missing or invalid promotion",
+            Self::NoCastlingRights => "the player is not allowed to castle on that side",
+            Self::CastlingThroughCheck => "cannot castle out of, or through, check",
+            Self::WouldLeaveKingInCheck => "the move would leave the king in check",
+        };
+        write!(f, "{}", error_msg)
+    }
+}
+
+impl core::error::Error for IllegalMove {}
+
+/// The reason for an edit of a [crate::board::ChessBoard] being rejected, e.g: by
+/// [crate::board::ChessBoard::add_piece].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EditError {
+    /// A piece is already standing on the square.
+    SquareOccupied,
+    /// No piece is standing on the square.
+    SquareEmpty,
+}
+
+impl core::fmt::Display for EditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error_msg = match self {
+            Self::SquareOccupied => "the square is already occupied",
+            Self::SquareEmpty => "the square is empty",
+        };
+        write!(f, "{}", error_msg)
+    }
+}
+
+impl core::error::Error for EditError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::IntoSquareError;
+    use crate::fen::FenError;
+
+    #[test]
+    fn display_and_error() {
+        let errors: Vec<Box<dyn core::error::Error>> = vec![
+            Box::new(IntoSquareError::EmptyBoard),
+            Box::new(IntoSquareError::TooManySquares),
+            Box::new(ValidationError::TooManyPieces),
+            Box::new(ValidationError::IncoherentPlieCount),
+            Box::new(MoveParseError::InvalidLength),
+            Box::new(MoveError::NoPieceToMove),
+            Box::new(MoveError::IllegalMove),
+            Box::new(MoveError::InvalidNotation(MoveParseError::InvalidSquare)),
+            Box::new(SanError::ParseError),
+            Box::new(SanError::Ambiguous),
+            Box::new(IllegalMove::NotYourPiece),
+            Box::new(IllegalMove::WouldLeaveKingInCheck),
+            Box::new(EditError::SquareOccupied),
+            Box::new(EditError::SquareEmpty),
+            Box::new(FenError::InvalidFen),
+            Box::new(FenError::InvalidPosition(ValidationError::MissingKing)),
+        ];
+        for error in errors {
+            assert!(!error.to_string().is_empty(), "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+
+        let error = MoveError::InvalidNotation(MoveParseError::InvalidSquare);
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            MoveParseError::InvalidSquare.to_string()
+        );
+        assert!(MoveError::IllegalMove.source().is_none());
+
+        let error = FenError::InvalidPosition(ValidationError::MissingKing);
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            ValidationError::MissingKing.to_string()
+        );
+        assert!(FenError::InvalidFen.source().is_none());
+    }
+}
@@ -0,0 +1,698 @@
+use super::ChessBoard;
+use crate::board::{Bitboard, File, Move, Piece, Square};
+use crate::movegen::{self, MoveList};
+use crate::utils::RandGen;
+
+impl ChessBoard {
+    /// Return the list of all legal [Move]s for the current player.
+    pub fn legal_moves(&self) -> MoveList {
+        let mut moves = MoveList::new();
+        self.moves_to_into(Bitboard::ALL, &mut moves);
+        moves
+    }
+
+    /// Return the number of legal [Move]s for the current player. The moves are generated in a
+    /// [MoveList] on the stack, without allocating.
+    pub fn count_legal_moves(&self) -> usize {
+        self.legal_moves().len()
+    }
+
+    /// Return true if the current player has at least one legal [Move]. Stops at the first legal
+    /// move found, looking at king moves first as they are the most likely to escape a check.
+    pub fn has_legal_move(&self) -> bool {
+        let color = self.current_player();
+        let has_piece_move = Piece::iter().any(|piece| {
+            self.occupancy(piece, color).into_iter().any(|start| {
+                let destinations =
+                    self.pseudo_legal_destinations(piece, start) - self.color_occupancy(color);
+                destinations.into_iter().any(|destination| {
+                    !movegen::in_check_after(self, Move::new(start, destination, None))
+                })
+            })
+        });
+        // If castling is legal, so is moving the king to its transit square, unless the castling
+        // rook is standing on it
+        has_piece_move || {
+            let mut moves = MoveList::new();
+            self.castling_moves_into(Bitboard::ALL, &mut moves);
+            !moves.is_empty()
+        }
+    }
+
+    /// Return true if the current player is in check, and has no legal [Move] to escape it.
+    pub fn is_checkmate(&self) -> bool {
+        !self.checkers().is_empty() && !self.has_legal_move()
+    }
+
+    /// Return true if playing the given legal [Move] checkmates the opponent.
+    pub fn gives_checkmate(&self, chess_move: Move) -> bool {
+        self.play_move(chess_move).is_checkmate()
+    }
+
+    /// Return the first legal [Move] which checkmates the opponent, or `None` if there is no mate
+    /// in one, e.g: to generate puzzles.
+    pub fn mate_in_one(&self) -> Option<Move> {
+        self.legal_moves()
+            .iter()
+            .copied()
+            .find(|&chess_move| self.gives_checkmate(chess_move))
+    }
+
+    /// Return one of the legal [Move]s picked at random, or `None` if there are none, e.g: to play
+    /// random games when fuzzing.
+    pub fn random_legal_move(&self, rng: &mut dyn RandGen) -> Option<Move> {
+        let moves = self.legal_moves();
+        if moves.is_empty() {
+            return None;
+        }
+        // The modulo bias is negligible, there are at most a few hundred moves
+        Some(moves[(rng.gen() % moves.len() as u64) as usize])
+    }
+
+    /// Generate all legal [Move]s for the current player whose destination is part of `targets`,
+    /// and add them at the end of the given [MoveList]. Restricting the destinations is useful to
+    /// generate captures of a given piece, or to block a check.
+    pub fn moves_to_into(&self, targets: Bitboard, moves: &mut MoveList) {
+        for piece in Piece::iter() {
+            self.piece_moves_to_into(piece, targets, moves);
+        }
+
+        self.castling_moves_into(targets, moves);
+    }
+
+    /// Generate all legal [Move]s for the current player's pieces of the given [Piece] type, and
+    /// add them at the end of the given [MoveList]. Castling moves are generated for the king.
+    /// Useful for staged move generation, e.g: to look at knight moves before the others.
+    pub fn generate_piece_moves(&self, piece: Piece, moves: &mut MoveList) {
+        self.piece_moves_to_into(piece, Bitboard::ALL, moves);
+        if piece == Piece::King {
+            self.castling_moves_into(Bitboard::ALL, moves);
+        }
+    }
+
+    /// Return the set of legal destinations for the current player's piece standing on `from`,
+    /// including castling and en-passant, e.g: to highlight them in a user interface. Returns an
+    /// empty [Bitboard] if `from` is not occupied by one of the current player's pieces.
+    pub fn legal_destinations(&self, from: Square) -> Bitboard {
+        let color = self.current_player();
+        let Some(piece) = self
+            .piece_on(from)
+            .filter(|_| self.color_occupancy(color).contains(from))
+        else {
+            return Bitboard::EMPTY;
+        };
+
+        let mut res = Bitboard::EMPTY;
+        for destination in self.pseudo_legal_destinations(piece, from) - self.color_occupancy(color)
+        {
+            if !movegen::in_check_after(self, Move::new(from, destination, None)) {
+                res |= destination;
+            }
+        }
+
+        if piece == Piece::King {
+            let mut castling = MoveList::new();
+            self.castling_moves_into(Bitboard::ALL, &mut castling);
+            for chess_move in &castling {
+                res |= chess_move.destination();
+            }
+        }
+
+        res
+    }
+
+    /// Generate the non-castling legal [Move]s for the current player's pieces of the given
+    /// [Piece] type whose destination is part of `targets`, and add them to the [MoveList].
+    fn piece_moves_to_into(&self, piece: Piece, targets: Bitboard, moves: &mut MoveList) {
+        let color = self.current_player();
+        let targets = targets - self.color_occupancy(color);
+
+        for start in self.occupancy(piece, color) {
+            let destinations = self.pseudo_legal_destinations(piece, start) & targets;
+            for destination in destinations {
+                let chess_move = Move::new(start, destination, None);
+                if movegen::in_check_after(self, chess_move) {
+                    continue;
+                }
+                if piece == Piece::Pawn && destination.rank() == color.promotion_rank() {
+                    for promotion in chess_move.promotions() {
+                        moves.push(promotion);
+                    }
+                } else {
+                    moves.push(chess_move);
+                }
+            }
+        }
+    }
+
+    /// Generate the legal castling [Move]s for the current player whose destination is part of
+    /// `targets`, and add them at the end of the given [MoveList]. The [CastleRights] must allow
+    /// it, the squares crossed by the king and the rook must be empty, and the king may not be in
+    /// check, nor cross or land on an attacked square.
+    pub(super) fn castling_moves_into(&self, targets: Bitboard, moves: &mut MoveList) {
+        let color = self.current_player();
+        let rights = self.castle_rights(color);
+        if !(rights.has_king_side() || rights.has_queen_side()) || !self.checkers().is_empty() {
+            return;
+        }
+
+        let rank = color.first_rank();
+        let king = self.castling_files(color).king();
+        let sides = [
+            (rights.has_king_side(), true, File::G),
+            (rights.has_queen_side(), false, File::C),
+        ];
+
+        for (allowed, king_side, destination) in sides {
+            let chess_move = self.castling_move(color, king_side);
+            if !allowed || !targets.contains(chess_move.destination()) {
+                continue;
+            }
+            let (rook, castle_blockers) = self.castling_path(king_side);
+            if !castle_blockers.is_disjoint(self.combined_occupancy()) {
+                continue;
+            }
+            // The rook does not shield the king anymore once it has moved
+            let occupancy = self.combined_occupancy() - rook;
+            let is_attacked = |file| {
+                let attackers = self.attackers_to(Square::new(file, rank), occupancy);
+                !attackers.is_disjoint(self.color_occupancy(!color))
+            };
+            // The king is not in check, but may not cross or land on an attacked square
+            let mut crossed = File::iter()
+                .filter(|&file| (king.min(destination)..=king.max(destination)).contains(&file));
+            if crossed.any(|file| file != king && is_attacked(file)) {
+                continue;
+            }
+            moves.push(chess_move);
+        }
+    }
+
+    /// Return the [Square] of the rook used by the current player to castle on the given side,
+    /// along with the [Square]s which must be empty to do so: the ones crossed by the king and
+    /// the rook on their way to their destination, apart from their own.
+    pub(super) fn castling_path(&self, king_side: bool) -> (Square, Bitboard) {
+        let color = self.current_player();
+        let rank = color.first_rank();
+        let files = self.castling_files(color);
+        let (rook, [rook_destination, king_destination]) = if king_side {
+            (files.king_side(), [File::F, File::G])
+        } else {
+            (files.queen_side(), [File::D, File::C])
+        };
+
+        let span = |lhs: File, rhs: File| {
+            File::iter()
+                .filter(|&file| (lhs.min(rhs)..=lhs.max(rhs)).contains(&file))
+                .fold(Bitboard::EMPTY, |acc, file| acc | Square::new(file, rank))
+        };
+        let path = span(files.king(), king_destination) | span(rook, rook_destination);
+
+        let rook = Square::new(rook, rank);
+        (rook, path - Square::new(files.king(), rank) - rook)
+    }
+
+    /// Return true if the given [Move] could be played by the current player in this position,
+    /// discarding king safety: the moving piece must belong to the current player, be able to
+    /// reach its destination given the current blockers, and any special move (castling,
+    /// en-passant, promotion) must have its pre-conditions met. Castling is represented as
+    /// returned by [ChessBoard::castling_move].
+    pub fn is_pseudo_legal(&self, chess_move: Move) -> bool {
+        let color = self.current_player();
+        let (start, destination) = (chess_move.start(), chess_move.destination());
+
+        if !self.color_occupancy(color).contains(start) {
+            return false;
+        }
+        // Unwrap is fine, we checked that the start square is occupied
+        let piece = self.piece_on(start).unwrap();
+
+        // Only pawns reaching the last rank can, and must, be promoted
+        let is_promoting = piece == Piece::Pawn && destination.rank() == color.promotion_rank();
+        match chess_move.promotion() {
+            None if is_promoting => return false,
+            Some(_) if !is_promoting => return false,
+            Some(Piece::King | Piece::Pawn) => return false,
+            _ => {}
+        }
+
+        // The king might move onto its castling rook's square, checked before occupancy
+        if let Some(king_side) = self.castling_side(color, chess_move) {
+            if piece == Piece::King {
+                let rights = self.castle_rights(color);
+                let allowed = if king_side {
+                    rights.has_king_side()
+                } else {
+                    rights.has_queen_side()
+                };
+                let (_, castle_blockers) = self.castling_path(king_side);
+                return allowed && castle_blockers.is_disjoint(self.combined_occupancy());
+            }
+        }
+
+        if self.color_occupancy(color).contains(destination) {
+            return false;
+        }
+
+        self.pseudo_legal_destinations(piece, start)
+            .contains(destination)
+    }
+
+    /// Compute the set of destinations for the current player's [Piece] standing on `start`, not
+    /// including castling, and discarding king safety. May include squares occupied by the
+    /// current player's pieces.
+    pub(super) fn pseudo_legal_destinations(&self, piece: Piece, start: Square) -> Bitboard {
+        let color = self.current_player();
+        let blockers = self.combined_occupancy();
+        match piece {
+            Piece::King => movegen::king_moves(start),
+            Piece::Queen => movegen::queen_moves(start, blockers),
+            Piece::Rook => movegen::rook_moves(start, blockers),
+            Piece::Bishop => movegen::bishop_moves(start, blockers),
+            Piece::Knight => movegen::knight_moves(start),
+            Piece::Pawn => {
+                let en_passant = self
+                    .en_passant()
+                    .map_or(Bitboard::EMPTY, Square::into_bitboard);
+                let captures = movegen::pawn_attacks(color, start)
+                    & (self.color_occupancy(!color) | en_passant);
+                let pushes = movegen::pawn_quiet_moves(color, start, blockers) - blockers;
+                captures | pushes
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{Color, IllegalMove, MoveKind};
+    use crate::fen::FromFen;
+
+    #[test]
+    fn pseudo_legal() {
+        let position = ChessBoard::default();
+        assert!(position.is_pseudo_legal(Move::new(Square::E2, Square::E4, None)));
+        assert!(position.is_pseudo_legal(Move::new(Square::E2, Square::E3, None)));
+        assert!(position.is_pseudo_legal(Move::new(Square::G1, Square::F3, None)));
+        // Not our piece
+        assert!(!position.is_pseudo_legal(Move::new(Square::E7, Square::E5, None)));
+        // Blocked by our own pieces
+        assert!(!position.is_pseudo_legal(Move::new(Square::F1, Square::C4, None)));
+        assert!(!position.is_pseudo_legal(Move::new(Square::E1, Square::E2, None)));
+        // Pawns can't push more than once
+        assert!(!position.is_pseudo_legal(Move::new(Square::E2, Square::E5, None)));
+        // No castling through pieces
+        assert!(!position.is_pseudo_legal(Move::new(Square::E1, Square::G1, None)));
+    }
+
+    #[test]
+    fn pseudo_legal_stale_move() {
+        let position = ChessBoard::default();
+        let stale = Move::new(Square::E2, Square::E4, None);
+        assert!(position.is_pseudo_legal(stale));
+        let position = position
+            .play_move(stale)
+            .play_move(Move::new(Square::E7, Square::E5, None));
+        // The pawn is not on its starting square anymore
+        assert!(!position.is_pseudo_legal(stale));
+    }
+
+    #[test]
+    fn pseudo_legal_special_moves() {
+        let position = ChessBoard::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        // Castling
+        assert!(position.is_pseudo_legal(Move::new(Square::E1, Square::G1, None)));
+        assert!(position.is_pseudo_legal(Move::new(Square::E1, Square::C1, None)));
+        // En-passant
+        assert!(position.is_pseudo_legal(Move::new(Square::E5, Square::D6, None)));
+        assert!(!position.is_pseudo_legal(Move::new(Square::E5, Square::F6, None)));
+        // Promotions
+        assert!(position.is_pseudo_legal(Move::new(Square::B7, Square::B8, Some(Piece::Queen))));
+        assert!(position.is_pseudo_legal(Move::new(Square::B7, Square::A8, Some(Piece::Knight))));
+        assert!(!position.is_pseudo_legal(Move::new(Square::B7, Square::B8, None)));
+        assert!(!position.is_pseudo_legal(Move::new(Square::B7, Square::B8, Some(Piece::King))));
+        assert!(!position.is_pseudo_legal(Move::new(Square::E5, Square::E6, Some(Piece::Queen))));
+        assert!(!position.is_pseudo_legal(Move::new(Square::A1, Square::A7, Some(Piece::Queen))));
+
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+        // No castling rights
+        assert!(!position.is_pseudo_legal(Move::new(Square::E1, Square::G1, None)));
+        assert!(!position.is_pseudo_legal(Move::new(Square::E1, Square::C1, None)));
+    }
+
+    #[test]
+    fn legal_moves_start_position() {
+        assert_eq!(ChessBoard::default().legal_moves().len(), 20);
+    }
+
+    #[test]
+    fn legal_moves_named_positions() {
+        assert_eq!(ChessBoard::kiwipete().legal_moves().len(), 48);
+        assert_eq!(ChessBoard::rook_endgame().legal_moves().len(), 14);
+        assert_eq!(ChessBoard::promotions().legal_moves().len(), 6);
+        assert_eq!(ChessBoard::under_promotion().legal_moves().len(), 44);
+    }
+
+    #[test]
+    fn count_legal_moves() {
+        for position in ChessBoard::test_positions().into_iter().chain([
+            // In check
+            ChessBoard::from_fen("4k3/2R5/8/8/8/8/3P1P2/r3K3 w - - 0 1").unwrap(),
+            // Checkmate
+            ChessBoard::from_fen("4k3/4Q3/4K3/8/8/8/8/8 b - - 0 1").unwrap(),
+        ]) {
+            assert_eq!(position.count_legal_moves(), position.legal_moves().len());
+        }
+        assert_eq!(ChessBoard::default().count_legal_moves(), 20);
+    }
+
+    #[test]
+    fn is_checkmate() {
+        assert!(!ChessBoard::default().is_checkmate());
+        // In check, but not mated
+        assert!(
+            !ChessBoard::from_fen("4k3/2R5/8/8/8/8/3P1P2/r3K3 w - - 0 1")
+                .unwrap()
+                .is_checkmate()
+        );
+        // Stalemate
+        assert!(!ChessBoard::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1")
+            .unwrap()
+            .is_checkmate());
+        assert!(ChessBoard::from_fen("4k3/4Q3/4K3/8/8/8/8/8 b - - 0 1")
+            .unwrap()
+            .is_checkmate());
+    }
+
+    #[test]
+    fn gives_checkmate() {
+        let position = ChessBoard::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let mate = Move::new(Square::A1, Square::A8, None);
+        assert!(position.gives_checkmate(mate));
+        for &chess_move in &position.legal_moves() {
+            assert_eq!(position.gives_checkmate(chess_move), chess_move == mate);
+        }
+        // The side to move after the mate is the one being mated
+        assert!(!position.is_checkmate());
+        assert!(position.play_move(mate).is_checkmate());
+    }
+
+    #[test]
+    fn mate_in_one() {
+        let position = ChessBoard::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        assert_eq!(
+            position.mate_in_one(),
+            Some(Move::new(Square::A1, Square::A8, None))
+        );
+        // The escape square makes the back-rank check harmless
+        let position = ChessBoard::from_fen("6k1/5pp1/7p/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        assert_eq!(position.mate_in_one(), None);
+        assert_eq!(ChessBoard::default().mate_in_one(), None);
+    }
+
+    #[test]
+    fn random_legal_move() {
+        use crate::utils::SimpleRng;
+
+        let mut rng = SimpleRng::new();
+        assert_eq!(
+            ChessBoard::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1")
+                .unwrap()
+                .random_legal_move(&mut rng),
+            None
+        );
+
+        let mut position = ChessBoard::default();
+        for _ in 0..500 {
+            let Some(chess_move) = position.random_legal_move(&mut rng) else {
+                position = ChessBoard::default();
+                continue;
+            };
+            assert!(position.is_legal(chess_move));
+            position = position.play_move(chess_move);
+            assert_eq!(position.validate(), Ok(()));
+            assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+        }
+    }
+
+    #[test]
+    fn legal_moves_castling() {
+        let king_side = Move::new(Square::E1, Square::G1, None);
+        let queen_side = Move::new(Square::E1, Square::C1, None);
+
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.contains(&king_side));
+        assert!(moves.contains(&queen_side));
+
+        // The king would cross an attacked square
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(!moves.contains(&king_side));
+        assert!(moves.contains(&queen_side));
+
+        // The king would land on an attacked square
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/2r5/R3K2R w KQkq - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.contains(&king_side));
+        assert!(!moves.contains(&queen_side));
+
+        // Only the rook crosses an attacked square
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/1r6/R3K2R w KQkq - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&queen_side));
+
+        // The king is in check
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/4r3/R3K2R w KQkq - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(!moves.contains(&king_side));
+        assert!(!moves.contains(&queen_side));
+
+        // Blocked by a piece
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/RN2K2R w KQkq - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.contains(&king_side));
+        assert!(!moves.contains(&queen_side));
+
+        // Castling with the rook on b1, which must cross c1 and d1
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/1R2K2R w HBha - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&queen_side));
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/NR2K2R w HBha - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&queen_side));
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/1RN1K2R w HBha - 0 1").unwrap();
+        assert!(!position.legal_moves().contains(&queen_side));
+
+        // The king would be attacked once the rook on b1 has moved
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/rR2K3 w B - 0 1").unwrap();
+        assert!(!position.legal_moves().contains(&queen_side));
+
+        // The king lands on the original square of the rook
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K1R1 w G - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&king_side));
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4KNR1 w G - 0 1").unwrap();
+        assert!(!position.legal_moves().contains(&king_side));
+    }
+
+    #[test]
+    fn legal_moves_castling_non_standard_rooks() {
+        for fen in [
+            "r3k2r/8/8/8/8/8/8/1R2K2R w HBha - 0 1",
+            "r3k2r/8/8/8/8/8/8/RR2K1R1 w GBha - 0 1",
+            "4k3/8/8/8/8/8/8/2R1KR2 w FC - 0 1",
+            "4k3/8/8/8/8/8/8/3RK3 w D - 0 1",
+            "4k3/8/8/8/8/8/8/rR2K3 w B - 0 1",
+            "1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1",
+            "4k3/8/8/8/8/8/8/5K1R w H - 0 1",
+            "4k3/8/8/8/8/8/8/RK6 w A - 0 1",
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            let moves = position.legal_moves();
+            assert_eq!(position.has_legal_move(), !moves.is_empty());
+            for start in Square::iter() {
+                for destination in Square::iter() {
+                    let chess_move = Move::new(start, destination, None);
+                    if moves.contains(&chess_move) {
+                        assert!(position.is_pseudo_legal(chess_move), "{:?}", chess_move);
+                    }
+                    assert_eq!(
+                        position.is_legal(chess_move),
+                        moves.contains(&chess_move),
+                        "{} {:?}",
+                        fen,
+                        chess_move
+                    );
+                }
+            }
+            for &chess_move in moves.iter() {
+                let played = position.play_move(chess_move);
+                assert!(played.is_valid(), "{} {:?}", fen, chess_move);
+                assert_eq!(played.zobrist_hash(), played.zobrist_from_scratch());
+            }
+        }
+    }
+
+    #[test]
+    fn legal_moves_castling_chess960() {
+        let position = ChessBoard::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1").unwrap();
+        let king_side = Move::new(Square::G1, Square::H1, None);
+        let queen_side = Move::new(Square::G1, Square::B1, None);
+        let moves = position.legal_moves();
+        assert!(moves.contains(&king_side));
+        assert!(moves.contains(&queen_side));
+        assert_eq!(position.castling_move(Color::White, true), king_side);
+        assert_eq!(position.parse_san("O-O-O"), Ok(queen_side));
+        assert_eq!(position.classify_move(king_side), MoveKind::Castle);
+
+        // Castling is told apart from a regular king move to the same square
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.contains(&Move::new(Square::F1, Square::G1, None)));
+        assert!(moves.contains(&Move::new(Square::F1, Square::H1, None)));
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/RK6 w A - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.contains(&Move::new(Square::B1, Square::C1, None)));
+        assert!(moves.contains(&Move::new(Square::B1, Square::A1, None)));
+
+        // The king may not cross an attacked square
+        let position = ChessBoard::from_fen("4kr2/8/8/8/8/8/8/1K5R w H - 0 1").unwrap();
+        assert!(!position.is_legal(Move::new(Square::B1, Square::H1, None)));
+        assert_eq!(
+            position.check_legality(Move::new(Square::B1, Square::H1, None)),
+            Err(IllegalMove::CastlingThroughCheck)
+        );
+    }
+
+    #[test]
+    fn legal_moves_pinned_piece() {
+        let position = ChessBoard::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        let moves = position.legal_moves();
+        assert!(moves.iter().all(|m| m.start() != Square::E2));
+        assert_eq!(moves.len(), 4);
+    }
+
+    #[test]
+    fn legal_moves_in_check() {
+        let position = ChessBoard::from_fen("4k3/2R5/8/8/8/8/3P1P2/r3K3 w - - 0 1").unwrap();
+        // Either block with the rook, or move out of check
+        let mut moves = position.legal_moves();
+        moves.sort();
+        assert_eq!(
+            moves[..],
+            [
+                Move::new(Square::C7, Square::C1, None),
+                Move::new(Square::E1, Square::E2, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn legal_moves_en_passant() {
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let en_passant = Move::new(Square::E5, Square::D6, None);
+        assert!(position.legal_moves().contains(&en_passant));
+        // Discovered check through the en-passant capture
+        let position = ChessBoard::from_fen("8/8/8/K2pP2r/8/8/8/4k3 w - d6 0 1").unwrap();
+        assert!(!position.legal_moves().contains(&en_passant));
+    }
+
+    #[test]
+    fn legal_moves_promotions() {
+        let position = ChessBoard::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let mut moves = MoveList::new();
+        position.moves_to_into(Square::A8.into_bitboard(), &mut moves);
+        assert_eq!(
+            moves[..],
+            Move::new(Square::A7, Square::A8, None).promotions()
+        );
+    }
+
+    #[test]
+    fn moves_to_single_target() {
+        let position = ChessBoard::kiwipete();
+        let mut moves = MoveList::new();
+        position.moves_to_into(Square::H3.into_bitboard(), &mut moves);
+        let mut moves = moves.to_vec();
+        moves.sort();
+        // Only captures of the pawn on h3 are generated
+        assert_eq!(
+            moves,
+            [
+                Move::new(Square::F3, Square::H3, None),
+                Move::new(Square::G2, Square::H3, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_piece_moves() {
+        let position = ChessBoard::default();
+        let mut moves = MoveList::new();
+        position.generate_piece_moves(Piece::Knight, &mut moves);
+        moves.sort();
+        let mut expected = [
+            Move::new(Square::B1, Square::A3, None),
+            Move::new(Square::B1, Square::C3, None),
+            Move::new(Square::G1, Square::F3, None),
+            Move::new(Square::G1, Square::H3, None),
+        ];
+        expected.sort();
+        assert_eq!(&moves[..], &expected[..]);
+
+        let mut moves = MoveList::new();
+        position.generate_piece_moves(Piece::Queen, &mut moves);
+        assert!(moves.is_empty());
+
+        // All piece types together make up the full list of legal moves
+        let position = ChessBoard::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        let mut moves = MoveList::new();
+        for piece in Piece::iter() {
+            position.generate_piece_moves(piece, &mut moves);
+        }
+        assert_eq!(moves.len(), position.count_legal_moves());
+    }
+
+    #[test]
+    fn has_legal_move() {
+        assert!(ChessBoard::default().has_legal_move());
+        // Checkmate
+        let position = ChessBoard::from_fen("4k3/4Q3/4K3/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(!position.has_legal_move());
+        // Stalemate
+        let position = ChessBoard::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert!(!position.has_legal_move());
+        // The king is stuck, but a pawn can still move
+        let position = ChessBoard::from_fen("k7/7p/1Q6/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert!(position.has_legal_move());
+        // In check, but the checker can be captured
+        let position = ChessBoard::from_fen("k7/1Q6/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert!(position.has_legal_move());
+    }
+
+    #[test]
+    fn legal_destinations() {
+        let position = ChessBoard::from_fen("4k3/4r3/8/8/8/7N/4N3/4K2R w K - 0 1").unwrap();
+        // Pinned knight
+        assert_eq!(position.legal_destinations(Square::E2), Bitboard::EMPTY);
+        // Free knight
+        assert_eq!(
+            position.legal_destinations(Square::H3),
+            Square::F2 | Square::F4 | Square::G1 | Square::G5
+        );
+        // Castling
+        assert!(position.legal_destinations(Square::E1).contains(Square::G1));
+        // Empty square, and opponent's piece
+        assert_eq!(position.legal_destinations(Square::D4), Bitboard::EMPTY);
+        assert_eq!(position.legal_destinations(Square::E7), Bitboard::EMPTY);
+
+        let position = ChessBoard::from_fen("4k3/8/8/8/4N3/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(position.legal_destinations(Square::E4).count(), 8);
+
+        // En-passant
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(
+            position.legal_destinations(Square::E5),
+            Square::D6 | Square::E6
+        );
+    }
+}
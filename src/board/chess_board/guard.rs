@@ -0,0 +1,73 @@
+use super::{ChessBoard, NonReversibleState};
+use crate::board::Move;
+
+/// A [Move] played on a [ChessBoard], which is undone when the guard is dropped unless
+/// [MoveGuard::commit] is called. Created by [ChessBoard::guarded_move].
+#[must_use = "the move is undone as soon as the guard is dropped"]
+pub struct MoveGuard<'a> {
+    board: &'a mut ChessBoard,
+    chess_move: Move,
+    state: Option<NonReversibleState>,
+}
+
+impl MoveGuard<'_> {
+    /// Keep the [Move] on the board.
+    pub fn commit(mut self) {
+        self.state = None;
+    }
+}
+
+/// Inspect the [ChessBoard] with the [Move] played.
+impl core::ops::Deref for MoveGuard<'_> {
+    type Target = ChessBoard;
+
+    fn deref(&self) -> &Self::Target {
+        self.board
+    }
+}
+
+/// Undo the [Move], unless it was committed.
+impl Drop for MoveGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            self.board.unplay_move(self.chess_move, state);
+        }
+    }
+}
+
+impl ChessBoard {
+    /// Play the given [Move] in place, returning a [MoveGuard] which undoes it when dropped.
+    pub fn guarded_move(&mut self, chess_move: Move) -> MoveGuard<'_> {
+        let state = self.play_move_inplace(chess_move);
+        MoveGuard {
+            board: self,
+            chess_move,
+            state: Some(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+
+    #[test]
+    fn drop_undoes_move() {
+        let mut position = ChessBoard::default();
+        let chess_move = Move::new(Square::E2, Square::E4, None);
+        {
+            let guard = position.guarded_move(chess_move);
+            assert_eq!(*guard, ChessBoard::default().play_move(chess_move));
+        }
+        assert_eq!(position, ChessBoard::default());
+    }
+
+    #[test]
+    fn commit_keeps_move() {
+        let mut position = ChessBoard::default();
+        let chess_move = Move::new(Square::E2, Square::E4, None);
+        position.guarded_move(chess_move).commit();
+        assert_eq!(position, ChessBoard::default().play_move(chess_move));
+    }
+}
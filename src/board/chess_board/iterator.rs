@@ -0,0 +1,69 @@
+use super::ChessBoard;
+use crate::board::{Bitboard, Color, Piece, Square};
+
+/// An [Iterator] over the pieces of a [ChessBoard], with their [Square] and [Color], see
+/// [ChessBoard::iter_pieces].
+pub struct ChessBoardIterator<'a> {
+    board: &'a ChessBoard,
+    squares: <Bitboard as IntoIterator>::IntoIter,
+}
+
+impl Iterator for ChessBoardIterator<'_> {
+    type Item = (Square, Piece, Color);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let square = self.squares.next()?;
+        // Unwraps are fine, we only iterate over occupied squares
+        let piece = self.board.piece_on(square).unwrap();
+        let color = self.board.color_on(square).unwrap();
+        Some((square, piece, color))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.squares.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ChessBoardIterator<'_> {}
+
+impl core::iter::FusedIterator for ChessBoardIterator<'_> {}
+
+impl ChessBoard {
+    /// Iterate over all pieces on the board, with their [Square] and [Color].
+    pub fn iter_pieces(&self) -> ChessBoardIterator<'_> {
+        ChessBoardIterator {
+            board: self,
+            squares: self.combined_occupancy().into_iter(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ChessBoard {
+    type IntoIter = ChessBoardIterator<'a>;
+    type Item = (Square, Piece, Color);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_pieces()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iter_pieces() {
+        let position = ChessBoard::default();
+        let mut count = 0;
+        for (square, piece, color) in &position {
+            assert_eq!(position.piece_on(square), Some(piece));
+            assert!(position.color_occupancy(color).contains(square));
+            count += 1;
+        }
+        assert_eq!(count, 32);
+        assert_eq!(position.iter_pieces().len(), 32);
+        assert!(position
+            .iter_pieces()
+            .any(|piece| piece == (Square::E8, Piece::King, Color::Black)));
+    }
+}
@@ -0,0 +1,227 @@
+use super::{ChessBoard, IllegalMove, NonReversibleState};
+use crate::board::{Bitboard, Move, Piece};
+use crate::movegen::{self, MoveList};
+
+impl ChessBoard {
+    /// Return true if the given [Move] is legal in the current position, i.e: it is part of
+    /// [ChessBoard::legal_moves].
+    pub fn is_legal(&self, chess_move: Move) -> bool {
+        self.check_legality(chess_move).is_ok()
+    }
+
+    /// Play the given [Move] in place if it is legal, return the [NonReversibleState] needed to
+    /// undo it. Otherwise, return Err([IllegalMove]) explaining why it was rejected, and leave
+    /// the board untouched.
+    pub fn make_move_checked(
+        &mut self,
+        chess_move: Move,
+    ) -> Result<NonReversibleState, IllegalMove> {
+        self.check_legality(chess_move)?;
+        Ok(self.play_move_inplace(chess_move))
+    }
+
+    /// Check that the given [Move] is legal, returning the first [IllegalMove] reason otherwise.
+    pub(crate) fn check_legality(&self, chess_move: Move) -> Result<(), IllegalMove> {
+        let color = self.current_player();
+        let (start, destination) = (chess_move.start(), chess_move.destination());
+
+        if !self.color_occupancy(color).contains(start) {
+            return Err(IllegalMove::NotYourPiece);
+        }
+        // Unwrap is fine, we checked that the start square is occupied
+        let piece = self.piece_on(start).unwrap();
+
+        let castle_side = self
+            .castling_side(color, chess_move)
+            .filter(|_| piece == Piece::King);
+        // The king might castle onto its rook's square, which is checked along with castling
+        if self.color_occupancy(color).contains(destination) && castle_side.is_none() {
+            return Err(IllegalMove::DestinationOccupied);
+        }
+
+        // Only pawns reaching the last rank can, and must, be promoted
+        let is_promoting = piece == Piece::Pawn && destination.rank() == color.promotion_rank();
+        match chess_move.promotion() {
+            None if is_promoting => return Err(IllegalMove::InvalidPromotion),
+            Some(_) if !is_promoting => return Err(IllegalMove::InvalidPromotion),
+            Some(Piece::King | Piece::Pawn) => return Err(IllegalMove::InvalidPromotion),
+            _ => {}
+        }
+
+        if let Some(king_side) = castle_side {
+            return self.check_castling_legality(chess_move, king_side);
+        }
+
+        if !self
+            .pseudo_legal_destinations(piece, start)
+            .contains(destination)
+        {
+            let blockers = Bitboard::EMPTY;
+            let unblocked = match piece {
+                Piece::Queen => movegen::queen_moves(start, blockers),
+                Piece::Rook => movegen::rook_moves(start, blockers),
+                Piece::Bishop => movegen::bishop_moves(start, blockers),
+                Piece::Pawn => movegen::pawn_quiet_moves(color, start, blockers),
+                Piece::King | Piece::Knight => Bitboard::EMPTY,
+            };
+            return Err(if unblocked.contains(destination) {
+                IllegalMove::BlockedPath
+            } else {
+                IllegalMove::UnreachableSquare
+            });
+        }
+
+        if movegen::in_check_after(self, chess_move) {
+            return Err(IllegalMove::WouldLeaveKingInCheck);
+        }
+
+        Ok(())
+    }
+
+    /// Check that the given castling [Move], on the given side, is legal.
+    fn check_castling_legality(
+        &self,
+        chess_move: Move,
+        king_side: bool,
+    ) -> Result<(), IllegalMove> {
+        let color = self.current_player();
+        let rights = self.castle_rights(color);
+        let allowed = if king_side {
+            rights.has_king_side()
+        } else {
+            rights.has_queen_side()
+        };
+        let (_, castle_blockers) = self.castling_path(king_side);
+
+        if !allowed {
+            return Err(IllegalMove::NoCastlingRights);
+        }
+        if !castle_blockers.is_disjoint(self.combined_occupancy()) {
+            return Err(IllegalMove::BlockedPath);
+        }
+
+        let mut moves = MoveList::new();
+        self.castling_moves_into(chess_move.destination().into_bitboard(), &mut moves);
+        if moves.is_empty() {
+            return Err(IllegalMove::CastlingThroughCheck);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn is_legal_agrees_with_legal_moves() {
+        for position in ChessBoard::test_positions() {
+            let legal_moves = position.legal_moves();
+            for start in Square::iter() {
+                for destination in Square::iter() {
+                    for promotion in [None, Some(Piece::Queen), Some(Piece::Knight)] {
+                        let chess_move = Move::new(start, destination, promotion);
+                        assert_eq!(
+                            position.is_legal(chess_move),
+                            legal_moves.contains(&chess_move),
+                            "{:?}",
+                            chess_move
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn make_move_checked() {
+        let mut position = ChessBoard::default();
+        let state = position
+            .make_move_checked(Move::new(Square::E2, Square::E4, None))
+            .unwrap();
+        position.unplay_move(Move::new(Square::E2, Square::E4, None), state);
+        assert_eq!(position, ChessBoard::default());
+    }
+
+    #[test]
+    fn make_move_checked_errors() {
+        let mut position =
+            ChessBoard::from_fen("r3k2r/1P6/8/8/1b6/4n3/P2P4/R3KN1R w KQk - 0 1").unwrap();
+        let original = position.clone();
+        for (chess_move, error) in [
+            (
+                Move::new(Square::E8, Square::G8, None),
+                IllegalMove::NotYourPiece,
+            ),
+            (
+                Move::new(Square::E3, Square::E4, None),
+                IllegalMove::NotYourPiece,
+            ),
+            (
+                Move::new(Square::A1, Square::E1, None),
+                IllegalMove::DestinationOccupied,
+            ),
+            (
+                Move::new(Square::F1, Square::F3, None),
+                IllegalMove::UnreachableSquare,
+            ),
+            (
+                Move::new(Square::D2, Square::C3, None),
+                IllegalMove::UnreachableSquare,
+            ),
+            (
+                Move::new(Square::A1, Square::A3, None),
+                IllegalMove::BlockedPath,
+            ),
+            (
+                Move::new(Square::E1, Square::G1, None),
+                IllegalMove::BlockedPath,
+            ),
+            (
+                Move::new(Square::B7, Square::B8, None),
+                IllegalMove::InvalidPromotion,
+            ),
+            (
+                Move::new(Square::B7, Square::B8, Some(Piece::King)),
+                IllegalMove::InvalidPromotion,
+            ),
+            (
+                Move::new(Square::D2, Square::D3, Some(Piece::Queen)),
+                IllegalMove::InvalidPromotion,
+            ),
+            (
+                Move::new(Square::D2, Square::D4, None),
+                IllegalMove::WouldLeaveKingInCheck,
+            ),
+            (
+                Move::new(Square::E1, Square::D1, None),
+                IllegalMove::WouldLeaveKingInCheck,
+            ),
+            (
+                Move::new(Square::E1, Square::C1, None),
+                IllegalMove::CastlingThroughCheck,
+            ),
+        ] {
+            assert_eq!(
+                position.make_move_checked(chess_move),
+                Err(error),
+                "{:?}",
+                chess_move
+            );
+            assert_eq!(position, original);
+        }
+
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1").unwrap();
+        assert_eq!(
+            position.check_legality(Move::new(Square::E1, Square::G1, None)),
+            Err(IllegalMove::NoCastlingRights)
+        );
+        assert_eq!(
+            position.check_legality(Move::new(Square::E1, Square::C1, None)),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use super::ChessBoard;
+use crate::board::{Color, Piece};
+use crate::eval::PieceValues;
+
+/// The pieces counted in a material signature, in order, see [ChessBoard::material_signature].
+const SIGNATURE_PIECES: [Piece; 5] = [
+    Piece::Queen,
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Knight,
+    Piece::Pawn,
+];
+
+impl ChessBoard {
+    /// Return the number of each non-king [Piece] for each [Color]: white's queens, rooks,
+    /// bishops, knights, and pawns, followed by black's. Positions with the same material share
+    /// the same signature, which makes it usable as a key for endgame or material tables.
+    pub fn material_signature(&self) -> [u8; 10] {
+        let mut res = [0; 10];
+        for color in Color::iter() {
+            for (index, piece) in SIGNATURE_PIECES.into_iter().enumerate() {
+                res[color.index() * SIGNATURE_PIECES.len() + index] =
+                    self.occupancy(piece, color).count() as u8;
+            }
+        }
+        res
+    }
+
+    /// Return the difference in material between the current player and its opponent, using
+    /// the given [PieceValues]. Kings are not counted.
+    pub fn material_balance(&self, values: &PieceValues) -> i32 {
+        let us = self.current_player();
+        SIGNATURE_PIECES
+            .into_iter()
+            .map(|piece| {
+                let ours = self.occupancy(piece, us).count() as i32;
+                let theirs = self.occupancy(piece, !us).count() as i32;
+                (ours - theirs) * values.value(piece)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn material_signature() {
+        assert_eq!(
+            ChessBoard::default().material_signature(),
+            [1, 2, 2, 2, 8, 1, 2, 2, 2, 8]
+        );
+
+        let kqk = ChessBoard::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        let krk = ChessBoard::from_fen("4k3/8/8/8/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert_eq!(kqk.material_signature(), [1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(krk.material_signature(), [0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        // Only material matters, not the placement of pieces
+        let other_kqk = ChessBoard::from_fen("8/8/3k4/8/8/2Q5/8/6K1 b - - 4 30").unwrap();
+        assert_eq!(kqk.material_signature(), other_kqk.material_signature());
+
+        // Colors are not interchangeable
+        let kkq = ChessBoard::from_fen("3qk3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_ne!(kqk.material_signature(), kkq.material_signature());
+    }
+
+    #[test]
+    fn material_balance() {
+        let values = PieceValues::DEFAULT;
+        assert_eq!(ChessBoard::default().material_balance(&values), 0);
+
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert_eq!(position.material_balance(&values), 900);
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+        assert_eq!(position.material_balance(&values), -900);
+
+        let position = ChessBoard::from_fen("3nk3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap();
+        assert_eq!(position.material_balance(&values), 0);
+        let values = values.with_value(Piece::Bishop, 350);
+        assert_eq!(position.material_balance(&values), 50);
+    }
+}
@@ -0,0 +1,96 @@
+use super::{ChessBoard, ChessBoardBuilder};
+use crate::board::{Color, Rank, Square};
+
+/// Mirror a [Square] vertically, e.g: A1 becomes A8.
+fn flip_square(square: Square) -> Square {
+    Square::new(
+        square.file(),
+        Rank::from_index(Rank::NUM_VARIANTS - 1 - square.rank_index()),
+    )
+}
+
+impl ChessBoard {
+    /// Return the board seen from the point of view of the side to move, i.e: mirrored
+    /// vertically with colors swapped when black is to move, so that white is always to move.
+    /// This allows writing evaluation code for a single side. When mirrored, the resulting
+    /// position is one ply later than the original, so that the half-move clock stays valid.
+    pub fn side_to_move_pov(&self) -> Self {
+        if self.current_player() == Color::White {
+            return self.clone();
+        }
+
+        let mut builder = ChessBoardBuilder::new();
+        for (square, piece, color) in self {
+            builder[flip_square(square)] = Some((piece, !color));
+        }
+        for color in Color::iter() {
+            builder.with_castle_rights(self.castle_rights(color), !color);
+            builder.with_castling_files(self.castling_files(color), !color);
+        }
+        if let Some(square) = self.en_passant() {
+            builder.with_en_passant(flip_square(square));
+        }
+        builder
+            .with_half_move_clock(self.half_move_clock())
+            // White to move on the turn after black's, i.e: one more ply than the original
+            .with_turn_count(self.total_plies() / 2 + 2)
+            .with_current_player(Color::White);
+
+        // Unwrap is fine, mirroring a valid position results in a valid position
+        builder.try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{Move, Piece};
+    use crate::fen::FromFen;
+
+    #[test]
+    fn side_to_move_pov_white() {
+        assert_eq!(
+            ChessBoard::kiwipete().side_to_move_pov(),
+            ChessBoard::kiwipete()
+        );
+    }
+
+    #[test]
+    fn side_to_move_pov_black() {
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/3pP3/8/8/4K2R b Kkq e3 3 10").unwrap();
+        let pov = position.side_to_move_pov();
+        assert_eq!(
+            pov,
+            ChessBoard::from_fen("4k2r/8/8/3Pp3/8/8/8/R3K2R w KQk e6 3 11").unwrap()
+        );
+        assert_eq!(pov.current_player(), Color::White);
+        assert_eq!(pov.piece_on(Square::D5), Some(Piece::Pawn));
+        assert!(pov.color_occupancy(Color::White).contains(Square::D5));
+        assert_eq!(pov.legal_moves().len(), position.legal_moves().len());
+        assert!(pov
+            .legal_moves()
+            .contains(&Move::new(Square::D5, Square::E6, None)));
+    }
+
+    #[test]
+    fn side_to_move_pov_castling_files() {
+        let position = ChessBoard::from_fen("1r2k2r/8/8/8/8/8/8/R3K1R1 b GAhb - 0 1").unwrap();
+        assert_eq!(
+            position.side_to_move_pov(),
+            ChessBoard::from_fen("r3k1r1/8/8/8/8/8/8/1R2K2R w HBga - 0 2").unwrap()
+        );
+    }
+
+    #[test]
+    fn side_to_move_pov_clock_equals_plies() {
+        let position = ChessBoard::default().play_move(Move::new(Square::G1, Square::F3, None));
+        assert_eq!(position.half_move_clock(), position.total_plies());
+        let pov = position.side_to_move_pov();
+        assert_eq!(
+            pov,
+            ChessBoard::from_fen("rnbqkb1r/pppppppp/5n2/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 1 2")
+                .unwrap()
+        );
+        assert_eq!(pov.total_plies(), position.total_plies() + 1);
+    }
+}
@@ -1,15 +1,67 @@
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
 use crate::movegen;
 
-use super::{Bitboard, CastleRights, Color, File, Move, Piece, Rank, Square};
+use super::{
+    Bitboard, CastleRights, CastlingFiles, Color, Direction, File, Move, Piece, Rank, Square,
+};
 
 mod builder;
 pub use builder::*;
 
+mod check_info;
+pub use check_info::*;
+
+mod draw;
+pub use draw::*;
+
+mod dump;
+
+mod edit;
+
 mod error;
 pub use error::*;
 
+mod generation;
+
+mod guard;
+pub use guard::*;
+
+mod iterator;
+pub use iterator::*;
+
+mod legality;
+
+mod material;
+
+mod mirror;
+
+mod move_kind;
+pub use move_kind::*;
+
+mod notation;
+
+mod ordering;
+
+mod perft;
+
+mod position_key;
+pub use position_key::*;
+
+mod positions;
+
+mod pretty;
+
+mod see;
+
+mod terminal;
+pub use terminal::*;
+
+pub mod zobrist;
+
 /// Represent an on-going chess game.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ChessBoard {
     /// A [Bitboard] of occupancy for each piece type, discarding color. Indexed by [Piece::index].
     piece_occupancy: [Bitboard; Piece::NUM_VARIANTS],
@@ -19,6 +71,9 @@ pub struct ChessBoard {
     combined_occupancy: Bitboard,
     /// The allowed [CastleRights] for either color. Indexed by [Color::index].
     castle_rights: [CastleRights; Color::NUM_VARIANTS],
+    /// The [CastlingFiles] of the rooks used to castle for either color, standard for the sides
+    /// which cannot castle. Indexed by [Color::index].
+    castling_files: [CastlingFiles; Color::NUM_VARIANTS],
     /// A potential en-passant attack.
     /// Either `None` if no double-step pawn move was made in the previous half-turn, or
     /// `Some(target_square)` if a double-step move was made.
@@ -29,15 +84,29 @@ pub struct ChessBoard {
     total_plies: u32, // Should be plenty.
     /// The current player turn.
     side: Color,
+    /// The Zobrist hash of the position, updated incrementally.
+    hash: u64,
+    /// The pieces checking the current player's king, updated whenever a move is (un-)played.
+    checkers: Bitboard,
 }
 
+// Make sure that [ChessBoard]s can be freely shared between threads.
+const _: () = {
+    use core::panic::{RefUnwindSafe, UnwindSafe};
+    const fn assert_thread_safe<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+    assert_thread_safe::<ChessBoard>()
+};
+
 /// The state which can't be reversed when doing/un-doing a [Move].
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NonReversibleState {
     castle_rights: [CastleRights; Color::NUM_VARIANTS],
+    castling_files: [CastlingFiles; Color::NUM_VARIANTS],
     en_passant: Option<Square>,
     half_move_clock: u32, // Should *probably* never go higher than 100.
     captured_piece: Option<Piece>,
+    hash: u64,
+    checkers: Bitboard,
 }
 
 impl ChessBoard {
@@ -53,12 +122,42 @@ impl ChessBoard {
         self.en_passant
     }
 
+    /// Return the en-passant target [Square] as it should be written in a FEN string. In `strict`
+    /// mode, it is only returned if one of the current player's pawns could capture on it.
+    pub fn en_passant_fen(&self, strict: bool) -> Option<Square> {
+        let square = self.en_passant()?;
+        if !strict {
+            return Some(square);
+        }
+        let color = self.current_player();
+        // A pawn can capture on the squares that an opposing pawn would attack it from
+        let origins = movegen::pawn_attacks(!color, square);
+        (!origins.is_disjoint(self.occupancy(Piece::Pawn, color))).then_some(square)
+    }
+
+    /// Return a copy of the board without its en-passant target [Square] if none of the current
+    /// player's pawns could capture on it, see [ChessBoard::en_passant_fen]. Positions which only
+    /// differ by such a "phantom" en-passant square already hash the same, and are equal once
+    /// normalized.
+    pub fn normalized(&self) -> Self {
+        let mut res = self.clone();
+        // The hash is left untouched, phantom en-passant squares are not part of it
+        res.en_passant = self.en_passant_fen(true);
+        res
+    }
+
     /// Return the [CastleRights] for the given [Color].
     #[inline(always)]
     pub fn castle_rights(&self, color: Color) -> CastleRights {
         self.castle_rights[color.index()]
     }
 
+    /// Return the [CastlingFiles] of the rooks used to castle for the given [Color].
+    #[inline(always)]
+    pub fn castling_files(&self, color: Color) -> CastlingFiles {
+        self.castling_files[color.index()]
+    }
+
     /// Return the [CastleRights] for the given [Color]. Allow mutations.
     #[inline(always)]
     fn castle_rights_mut(&mut self, color: Color) -> &mut CastleRights {
@@ -71,6 +170,27 @@ impl ChessBoard {
         self.piece_occupancy(piece) & self.color_occupancy(color)
     }
 
+    /// Get the [Bitboard] representing the rooks and queens of the given [Color].
+    #[inline(always)]
+    pub fn orthogonal_sliders(&self, color: Color) -> Bitboard {
+        (self.piece_occupancy(Piece::Rook) | self.piece_occupancy(Piece::Queen))
+            & self.color_occupancy(color)
+    }
+
+    /// Get the [Bitboard] representing the bishops and queens of the given [Color].
+    #[inline(always)]
+    pub fn diagonal_sliders(&self, color: Color) -> Bitboard {
+        (self.piece_occupancy(Piece::Bishop) | self.piece_occupancy(Piece::Queen))
+            & self.color_occupancy(color)
+    }
+
+    /// Get the [Bitboard] representing the bishops and knights of the given [Color].
+    #[inline(always)]
+    pub fn minor_pieces(&self, color: Color) -> Bitboard {
+        (self.piece_occupancy(Piece::Bishop) | self.piece_occupancy(Piece::Knight))
+            & self.color_occupancy(color)
+    }
+
     /// Get the [Bitboard] representing all pieces of the given [Piece] type, discarding color.
     #[inline(always)]
     pub fn piece_occupancy(&self, piece: Piece) -> Bitboard {
@@ -117,10 +237,105 @@ impl ChessBoard {
     }
 
     /// Return the [Bitboard] corresponding to all the opponent's pieces threatening the current
-    /// player's king.
+    /// player's king. It is computed once per move, and cached on the board.
     #[inline(always)]
     pub fn checkers(&self) -> Bitboard {
-        self.compute_checkers(self.current_player())
+        self.checkers
+    }
+
+    /// Return the [Piece] standing on the given [Square], or `None` if it is empty.
+    #[inline(always)]
+    pub fn piece_on(&self, square: Square) -> Option<Piece> {
+        Piece::iter().find(|&p| self.piece_occupancy(p).contains(square))
+    }
+
+    /// Return the [Color] of the piece standing on the given [Square], or `None` if it is empty.
+    #[inline(always)]
+    pub fn color_on(&self, square: Square) -> Option<Color> {
+        Color::iter().find(|&color| self.color_occupancy(color).contains(square))
+    }
+
+    /// Return a mailbox representation of the board, indexed by [Square::index], with the [Piece]
+    /// and [Color] standing on each square, or `None` if it is empty.
+    pub fn piece_list(&self) -> [Option<(Piece, Color)>; Square::NUM_VARIANTS] {
+        let mut res = [None; Square::NUM_VARIANTS];
+        for square in self.combined_occupancy() {
+            res[square.index()] = self.piece_on(square).zip(self.color_on(square));
+        }
+        res
+    }
+
+    /// Return the [Bitboard] of all pieces, of either [Color], attacking the given [Square].
+    /// Only pieces which are part of `occupancy` are considered, which is also used as the set of
+    /// blockers for sliding pieces. This allows computing x-ray attacks by removing pieces from it.
+    pub fn attackers_to(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let queens = self.piece_occupancy(Piece::Queen);
+
+        let bishops = {
+            let bishops = self.piece_occupancy(Piece::Bishop);
+            let bishop_attacks = movegen::bishop_moves(square, occupancy);
+            (queens | bishops) & bishop_attacks
+        };
+        let rooks = {
+            let rooks = self.piece_occupancy(Piece::Rook);
+            let rook_attacks = movegen::rook_moves(square, occupancy);
+            (queens | rooks) & rook_attacks
+        };
+        let knights = self.piece_occupancy(Piece::Knight) & movegen::knight_moves(square);
+        let kings = self.piece_occupancy(Piece::King) & movegen::king_moves(square);
+        let pawns = Color::iter()
+            .map(|color| {
+                // A pawn attacks the squares that an opposing pawn would attack it from
+                self.occupancy(Piece::Pawn, color) & movegen::pawn_attacks(!color, square)
+            })
+            .fold(Bitboard::EMPTY, |lhs, rhs| lhs | rhs);
+
+        (bishops | rooks | knights | kings | pawns) & occupancy
+    }
+
+    /// Return the number of pieces of the given [Color] attacking the given [Square], e.g: to
+    /// measure the pressure on the squares around a king.
+    #[inline(always)]
+    pub fn count_attackers(&self, square: Square, color: Color) -> u32 {
+        (self.attackers_to(square, self.combined_occupancy()) & self.color_occupancy(color)).count()
+    }
+
+    /// Return true if any piece of the given [Color] attacks the given [Square]. This is cheaper
+    /// than [ChessBoard::attackers_to], as it returns as soon as an attacker is found.
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        // A pawn attacks the squares that an opposing pawn would attack it from
+        if !(self.occupancy(Piece::Pawn, by) & movegen::pawn_attacks(!by, square)).is_empty() {
+            return true;
+        }
+        if !(self.occupancy(Piece::Knight, by) & movegen::knight_moves(square)).is_empty() {
+            return true;
+        }
+        if !(self.occupancy(Piece::King, by) & movegen::king_moves(square)).is_empty() {
+            return true;
+        }
+        let blockers = self.combined_occupancy();
+        if !(self.diagonal_sliders(by) & movegen::bishop_moves(square, blockers)).is_empty() {
+            return true;
+        }
+        !(self.orthogonal_sliders(by) & movegen::rook_moves(square, blockers)).is_empty()
+    }
+
+    /// Return the first piece found along a ray starting from the given [Square] in the given
+    /// [Direction], with its [Square] and [Color], or `None` if the ray reaches the edge of the
+    /// board. The starting square itself is not considered.
+    /// It does not make sense to use this function with knight-only directions, and it will panic in
+    /// debug-mode if it happens.
+    pub fn first_piece_on_ray(
+        &self,
+        from: Square,
+        dir: Direction,
+    ) -> Option<(Square, Piece, Color)> {
+        let occupancy = self.combined_occupancy();
+        let square = (movegen::ray_attacks(from, dir, occupancy) & occupancy).any_square()?;
+        // Unwraps are fine, the square is occupied
+        let piece = self.piece_on(square).unwrap();
+        let color = self.color_on(square).unwrap();
+        Some((square, piece, color))
     }
 
     /// Quickly add/remove a piece on the [Bitboard]s that are part of the [ChessBoard] state.
@@ -129,81 +344,151 @@ impl ChessBoard {
         *self.piece_occupancy_mut(piece) ^= square;
         *self.color_occupancy_mut(color) ^= square;
         self.combined_occupancy ^= square;
+        self.hash ^= zobrist::moved_piece(color, piece, square);
+    }
+
+    /// Re-compute the cached checkers of the current player, after the board was modified.
+    fn update_checkers(&mut self) {
+        // The board might be missing a king while it is being edited
+        self.checkers = if self.occupancy(Piece::King, self.current_player()).count() == 1 {
+            self.compute_checkers(self.current_player())
+        } else {
+            Bitboard::EMPTY
+        };
     }
 
     /// Compute the change of [CastleRights] from moving/taking a piece.
-    fn update_castling(&mut self, color: Color, piece: Piece, file: File) {
+    fn update_castling(&mut self, color: Color, piece: Piece, square: Square) {
         let original = self.castle_rights(color);
-        let new_rights = match (piece, file) {
-            (Piece::Rook, File::A) => original.without_queen_side(),
-            (Piece::Rook, File::H) => original.without_king_side(),
-            (Piece::King, _) => CastleRights::NoSide,
-            _ => return,
-        };
+        let files = self.castling_files(color);
+        let new_rights = original.after_move(color, files, piece, square);
         if new_rights != original {
             *self.castle_rights_mut(color) = new_rights;
+            self.castling_files[color.index()] = files.for_rights(new_rights);
         }
     }
 
+    /// Compute the en-passant target [Square] after playing the given [Move] with `move_piece`.
+    fn en_passant_after(&self, chess_move: Move, move_piece: Piece) -> Option<Square> {
+        let is_double_step = move_piece == Piece::Pawn
+            && chess_move.start().rank() == self.current_player().second_rank()
+            && chess_move.destination().rank() == self.current_player().fourth_rank();
+        is_double_step.then(|| {
+            Square::new(
+                chess_move.destination().file(),
+                self.current_player().third_rank(),
+            )
+        })
+    }
+
     /// Play the given [Move], return a copy of the board with the resulting state.
     #[inline(always)]
     pub fn play_move(&self, chess_move: Move) -> Self {
         let mut res = self.clone();
-        res.play_move_inplace(chess_move);
+        // The copy is never un-done, there is no need for its state
+        let _ = res.play_move_inplace(chess_move);
         res
     }
 
+    /// Play the given [Move], return a copy of the board with the resulting state. Return
+    /// Err([MoveError]) if the move does not start from one of the current player's pieces, or
+    /// if its promotion is invalid.
+    pub fn try_play_move(&self, chess_move: Move) -> Result<Self, MoveError> {
+        let move_piece = (self.color_occupancy(self.current_player()) & chess_move.start())
+            .any_square()
+            .and_then(|square| self.piece_on(square))
+            .ok_or(MoveError::NoPieceToMove)?;
+        self.validate_promotion(chess_move, move_piece)?;
+        Ok(self.play_move(chess_move))
+    }
+
+    /// Check that the given [Move] promotes if, and only if, it brings a pawn to the last rank.
+    fn validate_promotion(&self, chess_move: Move, move_piece: Piece) -> Result<(), MoveError> {
+        let last_rank = self.current_player().promotion_rank();
+        let reaches_last_rank =
+            move_piece == Piece::Pawn && chess_move.destination().rank() == last_rank;
+        match chess_move.promotion() {
+            None if reaches_last_rank => Err(MoveError::MissingPromotion),
+            None => Ok(()),
+            Some(Piece::King | Piece::Pawn) => Err(MoveError::InvalidPromotion),
+            Some(_) if !reaches_last_rank => Err(MoveError::InvalidPromotion),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Play the given [Move], return a copy of the board with the resulting state, along with the
+    /// [NonReversibleState] needed to undo the move on that copy.
+    #[inline(always)]
+    pub fn play_move_with_undo(&self, chess_move: Move) -> (Self, NonReversibleState) {
+        let mut res = self.clone();
+        let state = res.play_move_inplace(chess_move);
+        (res, state)
+    }
+
     /// Play the given [Move] in place, returning all non-revertible state (e.g: en-passant,
-    /// etc...).
+    /// etc...). See [ChessBoard::guarded_move] for a version which cannot forget to undo the move.
     #[inline(always)]
+    #[must_use = "the returned state is needed to undo the move"]
     pub fn play_move_inplace(&mut self, chess_move: Move) -> NonReversibleState {
         let opponent = !self.current_player();
         let move_piece = Piece::iter()
-            .find(|&p| !(self.piece_occupancy(p) & chess_move.start()).is_empty())
+            .find(|&p| self.piece_occupancy(p).contains(chess_move.start()))
             .unwrap();
+        debug_assert_eq!(self.validate_promotion(chess_move, move_piece), Ok(()));
+        let is_en_passant =
+            move_piece == Piece::Pawn && Some(chess_move.destination()) == self.en_passant();
+        let captured_square = if is_en_passant {
+            Square::new(chess_move.destination().file(), chess_move.start().rank())
+        } else {
+            chess_move.destination()
+        };
         let captured_piece = Piece::iter()
             .skip(1) // No need to check for the king here
-            .find(|&p| !(self.occupancy(p, opponent) & chess_move.destination()).is_empty());
-        let is_double_step = move_piece == Piece::Pawn
-            && chess_move.start().rank() == self.current_player().second_rank()
-            && chess_move.destination().rank() == self.current_player().fourth_rank();
+            .find(|&p| self.occupancy(p, opponent).contains(captured_square));
 
         // Save non-revertible state
         let state = NonReversibleState {
             castle_rights: self.castle_rights,
+            castling_files: self.castling_files,
             en_passant: self.en_passant,
             half_move_clock: self.half_move_clock,
             captured_piece,
+            hash: self.hash,
+            checkers: self.checkers,
         };
 
+        // Must be computed before the castling rights, and their rooks, are updated
+        let castling = self.castling_squares(self.current_player(), chess_move);
+
         // Non-revertible state modification
+        self.hash ^= self.non_reversible_zobrist();
         if captured_piece.is_some() || move_piece == Piece::Pawn {
             self.half_move_clock = 0;
         } else {
             self.half_move_clock += 1;
         }
-        if is_double_step {
-            let target_square = Square::new(
-                chess_move.destination().file(),
-                self.current_player().third_rank(),
-            );
-            self.en_passant = Some(target_square);
-        } else {
-            self.en_passant = None;
-        }
-        self.update_castling(self.current_player(), move_piece, chess_move.start().file());
+        self.en_passant = self.en_passant_after(chess_move, move_piece);
+        self.update_castling(self.current_player(), move_piece, chess_move.start());
         if let Some(piece) = captured_piece {
-            self.xor(opponent, piece, chess_move.destination());
+            self.xor(opponent, piece, captured_square);
             // If a rook is captured, it loses its castling rights
-            self.update_castling(opponent, piece, chess_move.destination().file());
+            self.update_castling(opponent, piece, captured_square);
         }
 
         // Revertible state modification
+        // The rook is moved first, the king might be castling onto its original square
+        if let Some((_, (rook_start, rook_destination))) = castling {
+            self.xor(self.current_player(), Piece::Rook, rook_start);
+            self.xor(self.current_player(), Piece::Rook, rook_destination);
+        }
+        let destination = castling.map_or(chess_move.destination(), |(king, _)| king);
         let dest_piece = chess_move.promotion().unwrap_or(move_piece);
         self.xor(self.current_player(), move_piece, chess_move.start());
-        self.xor(self.current_player(), dest_piece, chess_move.destination());
+        self.xor(self.current_player(), dest_piece, destination);
         self.total_plies += 1;
         self.side = !self.side;
+        self.update_checkers();
+        self.hash ^= self.non_reversible_zobrist() ^ zobrist::side_to_move();
 
         state
     }
@@ -214,25 +499,43 @@ impl ChessBoard {
     pub fn unplay_move(&mut self, chess_move: Move, previous: NonReversibleState) {
         // Restore non-revertible state
         self.castle_rights = previous.castle_rights;
+        self.castling_files = previous.castling_files;
         self.en_passant = previous.en_passant;
         self.half_move_clock = previous.half_move_clock;
 
+        // A castling king does not land on the destination of its move
+        let castling = self.castling_squares(!self.current_player(), chess_move);
+        let destination = castling.map_or(chess_move.destination(), |(king, _)| king);
         let move_piece = Piece::iter()
             // We're looking for the *destination* as this is *undoing* the move
-            .find(|&p| !(self.piece_occupancy(p) & chess_move.destination()).is_empty())
+            .find(|&p| self.piece_occupancy(p).contains(destination))
             .unwrap();
 
+        // Restore revertible state
+        let start_piece = chess_move.promotion().map_or(move_piece, |_| Piece::Pawn);
+
         if let Some(piece) = previous.captured_piece {
+            let is_en_passant =
+                start_piece == Piece::Pawn && Some(chess_move.destination()) == self.en_passant();
+            let captured_square = if is_en_passant {
+                Square::new(chess_move.destination().file(), chess_move.start().rank())
+            } else {
+                chess_move.destination()
+            };
             // The capture affected the *current* player, from our post-move POV
-            self.xor(self.current_player(), piece, chess_move.destination());
+            self.xor(self.current_player(), piece, captured_square);
         }
 
-        // Restore revertible state
-        let start_piece = chess_move.promotion().map_or(move_piece, |_| Piece::Pawn);
-        self.xor(!self.current_player(), move_piece, chess_move.destination());
+        self.xor(!self.current_player(), move_piece, destination);
         self.xor(!self.current_player(), start_piece, chess_move.start());
+        if let Some((_, (rook_start, rook_destination))) = castling {
+            self.xor(!self.current_player(), Piece::Rook, rook_destination);
+            self.xor(!self.current_player(), Piece::Rook, rook_start);
+        }
         self.total_plies -= 1;
         self.side = !self.side;
+        self.checkers = previous.checkers;
+        self.hash = previous.hash;
     }
 
     /// Return true if the current state of the board looks valid, false if something is definitely
@@ -243,31 +546,52 @@ impl ChessBoard {
 
     /// Validate the state of the board. Return Err([ValidationError]) if an issue is found.
     pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.run_validation(ControlFlow::Break) {
+            ControlFlow::Break(error) => Err(error),
+            ControlFlow::Continue(()) => Ok(()),
+        }
+    }
+
+    /// Validate the state of the board, without stopping at the first issue. Return the list of
+    /// all [ValidationError] that were found, in the order they are checked.
+    pub fn validate_all(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let _ = self.run_validation(|error| {
+            errors.push(error);
+            ControlFlow::<()>::Continue(())
+        });
+        errors
+    }
+
+    /// Run all validation checks, calling `report` on each [ValidationError] that is found, and
+    /// stopping early if it returns [ControlFlow::Break].
+    fn run_validation<B>(
+        &self,
+        mut report: impl FnMut(ValidationError) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
         // The current plie count should be odd on white's turn, and vice-versa.
         if self.total_plies() % 2 != self.current_player().index() as u32 {
-            return Err(ValidationError::IncoherentPlieCount);
+            report(ValidationError::IncoherentPlieCount)?;
         }
 
         // Make sure the clocks are in agreement.
         if self.half_move_clock() > self.total_plies() {
-            return Err(ValidationError::HalfMoveClockTooHigh);
+            report(ValidationError::HalfMoveClockTooHigh)?;
         }
 
         // Don't overlap pieces.
         for piece in Piece::iter() {
-            #[allow(clippy::collapsible_if)]
-            for other in Piece::iter() {
-                if piece != other {
-                    if !(self.piece_occupancy(piece) & self.piece_occupancy(other)).is_empty() {
-                        return Err(ValidationError::OverlappingPieces);
-                    }
+            // Only look at each pair once, to report each overlap a single time.
+            for other in Piece::iter().skip(piece.index() + 1) {
+                if !(self.piece_occupancy(piece) & self.piece_occupancy(other)).is_empty() {
+                    report(ValidationError::OverlappingPieces)?;
                 }
             }
         }
 
         // Don't overlap colors.
         if !(self.color_occupancy(Color::White) & self.color_occupancy(Color::Black)).is_empty() {
-            return Err(ValidationError::OverlappingColors);
+            report(ValidationError::OverlappingColors)?;
         }
 
         // Calculate the union of all pieces.
@@ -276,12 +600,12 @@ impl ChessBoard {
 
         // Ensure that the pre-computed version is accurate.
         if combined != self.combined_occupancy() {
-            return Err(ValidationError::ErroneousCombinedOccupancy);
+            report(ValidationError::ErroneousCombinedOccupancy)?;
         }
 
         // Ensure that all pieces belong to a color, and no color has pieces that don't exist.
         if combined != (self.color_occupancy(Color::White) | self.color_occupancy(Color::Black)) {
-            return Err(ValidationError::ErroneousCombinedOccupancy);
+            report(ValidationError::ErroneousCombinedOccupancy)?;
         }
 
         for color in Color::iter() {
@@ -295,18 +619,18 @@ impl ChessBoard {
                     _ => count <= 10,
                 };
                 if !possible {
-                    return Err(ValidationError::TooManyPieces);
+                    report(ValidationError::TooManyPieces)?;
                 }
             }
 
             // Check that we have a king
             if self.occupancy(Piece::King, color).count() != 1 {
-                return Err(ValidationError::MissingKing);
+                report(ValidationError::MissingKing)?;
             }
 
             // Check that don't have too many pieces in total
             if self.color_occupancy(color).count() > 16 {
-                return Err(ValidationError::TooManyPieces);
+                report(ValidationError::TooManyPieces)?;
             }
         }
 
@@ -315,45 +639,55 @@ impl ChessBoard {
             & (Rank::First.into_bitboard() | Rank::Eighth.into_bitboard()))
         .is_empty()
         {
-            return Err(ValidationError::InvalidPawnPosition);
+            report(ValidationError::InvalidPawnPosition)?;
         }
 
         // Verify that rooks and kings that are allowed to castle have not been moved.
         for color in Color::iter() {
             let castle_rights = self.castle_rights(color);
 
-            // Nothing to check if there are no castlings allowed.
+            // The castling rooks must stand on either side of the king, the files of the sides
+            // which cannot castle must be left to their standard value.
+            let files = self.castling_files(color);
+            if (castle_rights.has_king_side() && files.king_side() <= files.king())
+                || (castle_rights.has_queen_side() && files.queen_side() >= files.king())
+                || files.for_rights(castle_rights) != files
+            {
+                report(ValidationError::InvalidCastlingRights)?;
+            }
+
+            // Nothing else to check if there are no castlings allowed.
             if castle_rights == CastleRights::NoSide {
                 continue;
             }
 
             let actual_rooks = self.occupancy(Piece::Rook, color);
-            let expected_rooks = castle_rights.unmoved_rooks(color);
+            let expected_rooks = castle_rights.unmoved_rooks(color, files);
             // We must check the intersection, in case there are more than 2 rooks on the board.
             if (expected_rooks & actual_rooks) != expected_rooks {
-                return Err(ValidationError::InvalidCastlingRights);
+                report(ValidationError::InvalidCastlingRights)?;
             }
 
             let actual_king = self.occupancy(Piece::King, color);
-            let expected_king = Square::new(File::E, color.first_rank());
-            // We have checked that there is exactly one king, no need for intersecting the sets.
+            let expected_king = Square::new(files.king(), color.first_rank());
+            // If there is not exactly one king, this was already reported, and also fails here.
             if actual_king != expected_king.into_bitboard() {
-                return Err(ValidationError::InvalidCastlingRights);
+                report(ValidationError::InvalidCastlingRights)?;
             }
         }
 
         // En-passant validation
         if let Some(square) = self.en_passant() {
             // Must be empty
-            if !(self.combined_occupancy() & square).is_empty() {
-                return Err(ValidationError::InvalidEnPassant);
+            if self.combined_occupancy().contains(square) {
+                report(ValidationError::InvalidEnPassant)?;
             }
 
             let opponent = !self.current_player();
 
             // Must be on the opponent's third rank
-            if (square & opponent.third_rank().into_bitboard()).is_empty() {
-                return Err(ValidationError::InvalidEnPassant);
+            if square.rank() != self.current_player().en_passant_rank() {
+                report(ValidationError::InvalidEnPassant)?;
             }
 
             // Must be behind a pawn
@@ -363,24 +697,94 @@ impl ChessBoard {
                 .backward_direction()
                 .move_board(square.into_bitboard());
             if (opponent_pawns & double_pushed_pawn).is_empty() {
-                return Err(ValidationError::InvalidEnPassant);
+                report(ValidationError::InvalidEnPassant)?;
             }
         }
 
+        // The remaining checks need to know where a given color's king is.
+        let has_single_king = |color| self.occupancy(Piece::King, color).count() == 1;
+
         // Check that kings don't touch each other.
-        let white_king = self.occupancy(Piece::King, Color::White);
-        let black_king = self.occupancy(Piece::King, Color::Black);
-        // Unwrap is fine, we already checked that there is exactly one king of each color
-        if !(movegen::king_moves(white_king.try_into().unwrap()) & black_king).is_empty() {
-            return Err(ValidationError::NeighbouringKings);
+        if has_single_king(Color::White) && has_single_king(Color::Black) {
+            let white_king = self.occupancy(Piece::King, Color::White);
+            let black_king = self.occupancy(Piece::King, Color::Black);
+            // Unwrap is fine, we already checked that there is exactly one king of each color
+            if !(movegen::king_moves(white_king.try_into().unwrap()) & black_king).is_empty() {
+                report(ValidationError::NeighbouringKings)?;
+            }
         }
 
         // Check that the opponent is not currently in check.
-        if !self.compute_checkers(!self.current_player()).is_empty() {
-            return Err(ValidationError::OpponentInCheck);
+        let opponent = !self.current_player();
+        if has_single_king(opponent) && !self.compute_checkers(opponent).is_empty() {
+            report(ValidationError::OpponentInCheck)?;
         }
 
-        Ok(())
+        ControlFlow::Continue(())
+    }
+
+    /// Return the [Move] with which the given [Color] castles on the given side. When the king
+    /// starts on [File::E], castling is represented as the king moving two squares towards the
+    /// rook, as in standard chess. Otherwise, it is represented as the king moving onto its
+    /// castling rook, as in Chess960, which cannot be mistaken for a regular king move.
+    pub fn castling_move(&self, color: Color, king_side: bool) -> Move {
+        let files = self.castling_files(color);
+        let rank = color.first_rank();
+        let destination = match (files.king(), king_side) {
+            (File::E, true) => File::G,
+            (File::E, false) => File::C,
+            (_, true) => files.king_side(),
+            (_, false) => files.queen_side(),
+        };
+        Move::new(
+            Square::new(files.king(), rank),
+            Square::new(destination, rank),
+            None,
+        )
+    }
+
+    /// If the given [Move] by the given [Color] has the shape of a castling move, see
+    /// [ChessBoard::castling_move], return whether it castles king-side. The moving piece is not
+    /// checked, and the [CastleRights] only are when the king does not start on [File::E]: it
+    /// could otherwise be a regular king move.
+    pub(crate) fn castling_side(&self, color: Color, chess_move: Move) -> Option<bool> {
+        let rights = self.castle_rights(color);
+        let is_standard = self.castling_files(color).king() == File::E;
+        [
+            (true, rights.has_king_side()),
+            (false, rights.has_queen_side()),
+        ]
+        .into_iter()
+        .filter(|&(_, allowed)| allowed || is_standard)
+        .map(|(king_side, _)| king_side)
+        .find(|&king_side| self.castling_move(color, king_side) == chess_move)
+    }
+
+    /// If the given [Move] by the given [Color] is a castling move allowed by its [CastleRights],
+    /// return the destination [Square] of the king, and the start and destination [Square] of the
+    /// rook. The king lands on the G or C file, and the rook next to it on the F or D file.
+    pub(crate) fn castling_squares(
+        &self,
+        color: Color,
+        chess_move: Move,
+    ) -> Option<(Square, (Square, Square))> {
+        let king_side = self.castling_side(color, chess_move)?;
+        let rights = self.castle_rights(color);
+        let files = self.castling_files(color);
+        let rank = color.first_rank();
+        let (rook, [rook_destination, king_destination]) = if king_side {
+            rights
+                .has_king_side()
+                .then_some((files.king_side(), [File::F, File::G]))?
+        } else {
+            rights
+                .has_queen_side()
+                .then_some((files.queen_side(), [File::D, File::C]))?
+        };
+        Some((
+            Square::new(king_destination, rank),
+            (Square::new(rook, rank), Square::new(rook_destination, rank)),
+        ))
     }
 
     /// Compute all pieces that are currently threatening the given [Color]'s king.
@@ -393,16 +797,12 @@ impl ChessBoard {
         // No need to remove our pieces from the generated moves, we just want to check if we
         // intersect with the opponent's pieces, rather than generate only valid moves.
         let bishops = {
-            let queens = self.occupancy(Piece::Queen, opponent);
-            let bishops = self.occupancy(Piece::Bishop, opponent);
             let bishop_attacks = movegen::bishop_moves(king, self.combined_occupancy());
-            (queens | bishops) & bishop_attacks
+            self.diagonal_sliders(opponent) & bishop_attacks
         };
         let rooks = {
-            let queens = self.occupancy(Piece::Queen, opponent);
-            let rooks = self.occupancy(Piece::Rook, opponent);
             let rook_attacks = movegen::rook_moves(king, self.combined_occupancy());
-            (queens | rooks) & rook_attacks
+            self.orthogonal_sliders(opponent) & rook_attacks
         };
         let knights = {
             let knights = self.occupancy(Piece::Knight, opponent);
@@ -423,7 +823,7 @@ impl ChessBoard {
 /// "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1" FEN string
 impl Default for ChessBoard {
     fn default() -> Self {
-        Self {
+        let mut res = Self {
             piece_occupancy: [
                 // King
                 Square::E1 | Square::E8,
@@ -447,11 +847,16 @@ impl Default for ChessBoard {
                 | Rank::Seventh.into_bitboard()
                 | Rank::Eighth.into_bitboard(),
             castle_rights: [CastleRights::BothSides; Color::NUM_VARIANTS],
+            castling_files: [CastlingFiles::STANDARD; Color::NUM_VARIANTS],
             en_passant: None,
             half_move_clock: 0,
             total_plies: 0,
             side: Color::White,
-        }
+            hash: 0,
+            checkers: Bitboard::EMPTY,
+        };
+        res.hash = res.zobrist_from_scratch();
+        res
     }
 }
 
@@ -467,6 +872,189 @@ mod test {
         assert!(default_position.is_valid());
     }
 
+    #[test]
+    fn is_valid_agrees_with_validate() {
+        let position = {
+            let mut builder = ChessBoardBuilder::new();
+            builder[Square::E1] = Some((Piece::King, Color::White));
+            builder[Square::E8] = Some((Piece::King, Color::Black));
+            let mut board: ChessBoard = builder.try_into().unwrap();
+            board.half_move_clock = 42;
+            board
+        };
+        assert!(!position.is_valid());
+        assert_eq!(
+            position.validate(),
+            Err(ValidationError::HalfMoveClockTooHigh)
+        );
+        assert!(ChessBoard::default().is_valid());
+        assert_eq!(ChessBoard::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn grouped_occupancy() {
+        let position = ChessBoard::kiwipete();
+        for color in Color::iter() {
+            let occupancy = |piece| position.occupancy(piece, color);
+            assert_eq!(
+                position.orthogonal_sliders(color),
+                occupancy(Piece::Rook) | occupancy(Piece::Queen)
+            );
+            assert_eq!(
+                position.diagonal_sliders(color),
+                occupancy(Piece::Bishop) | occupancy(Piece::Queen)
+            );
+            assert_eq!(
+                position.minor_pieces(color),
+                occupancy(Piece::Bishop) | occupancy(Piece::Knight)
+            );
+        }
+        assert_eq!(
+            ChessBoard::default().orthogonal_sliders(Color::White),
+            Square::A1 | Square::D1 | Square::H1
+        );
+    }
+
+    #[test]
+    fn count_attackers() {
+        let position = ChessBoard::from_fen("4k3/8/8/3p4/8/2N5/8/3RK3 w - - 0 1").unwrap();
+        assert_eq!(position.count_attackers(Square::D5, Color::White), 2);
+        assert_eq!(position.count_attackers(Square::D5, Color::Black), 0);
+        assert_eq!(position.count_attackers(Square::E4, Color::Black), 1);
+        assert_eq!(position.count_attackers(Square::H8, Color::White), 0);
+    }
+
+    #[test]
+    fn is_square_attacked() {
+        for position in [ChessBoard::default(), ChessBoard::kiwipete()] {
+            for square in Square::iter() {
+                for color in Color::iter() {
+                    let attackers = position.attackers_to(square, position.combined_occupancy())
+                        & position.color_occupancy(color);
+                    assert_eq!(
+                        position.is_square_attacked(square, color),
+                        !attackers.is_empty()
+                    );
+                }
+            }
+        }
+
+        let king_side = Move::new(Square::E1, Square::G1, None);
+        // The bishop attacks f1, the knight attacks g1
+        for fen in [
+            "4k3/8/b7/8/8/8/8/4K2R w K - 0 1",
+            "4k3/8/8/8/8/7n/8/4K2R w K - 0 1",
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            assert!(
+                position.is_square_attacked(Square::F1, Color::Black)
+                    || position.is_square_attacked(Square::G1, Color::Black)
+            );
+            assert!(!position.is_square_attacked(Square::E1, Color::Black));
+            assert!(!position.legal_moves().contains(&king_side));
+        }
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&king_side));
+    }
+
+    #[test]
+    fn first_piece_on_ray() {
+        let position = ChessBoard::kiwipete();
+        assert_eq!(
+            position.first_piece_on_ray(Square::E1, Direction::North),
+            Some((Square::E2, Piece::Bishop, Color::White))
+        );
+        assert_eq!(
+            position.first_piece_on_ray(Square::A1, Direction::North),
+            Some((Square::A2, Piece::Pawn, Color::White))
+        );
+        // Skips over the empty squares
+        assert_eq!(
+            position.first_piece_on_ray(Square::F3, Direction::North),
+            Some((Square::F6, Piece::Knight, Color::Black))
+        );
+        assert_eq!(
+            position.first_piece_on_ray(Square::D2, Direction::NorthWest),
+            Some((Square::C3, Piece::Knight, Color::White))
+        );
+        assert_eq!(
+            position.first_piece_on_ray(Square::F3, Direction::East),
+            Some((Square::H3, Piece::Pawn, Color::Black))
+        );
+        assert_eq!(
+            position.first_piece_on_ray(Square::G3, Direction::NorthEast),
+            None
+        );
+        assert_eq!(
+            position.first_piece_on_ray(Square::H1, Direction::East),
+            None
+        );
+    }
+
+    #[test]
+    fn piece_list() {
+        for position in [ChessBoard::default(), ChessBoard::kiwipete()] {
+            let pieces = position.piece_list();
+            for square in Square::iter() {
+                let expected = position.piece_on(square).map(|piece| {
+                    let color = Color::iter()
+                        .find(|&color| !(position.color_occupancy(color) & square).is_empty())
+                        .unwrap();
+                    (piece, color)
+                });
+                assert_eq!(pieces[square.index()], expected);
+            }
+        }
+        let pieces = ChessBoard::default().piece_list();
+        assert_eq!(
+            pieces[Square::E1.index()],
+            Some((Piece::King, Color::White))
+        );
+        assert_eq!(
+            pieces[Square::D8.index()],
+            Some((Piece::Queen, Color::Black))
+        );
+        assert_eq!(pieces[Square::E4.index()], None);
+    }
+
+    #[test]
+    fn color_on() {
+        let position = ChessBoard::default();
+        assert_eq!(position.color_on(Square::E1), Some(Color::White));
+        assert_eq!(position.color_on(Square::D8), Some(Color::Black));
+        assert_eq!(position.color_on(Square::E4), None);
+    }
+
+    #[test]
+    fn hash() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        let hash = |board: &ChessBoard| {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+        let first = ChessBoard::default()
+            .play_move(Move::new(Square::G1, Square::F3, None))
+            .play_move(Move::new(Square::G8, Square::F6, None))
+            .play_move(Move::new(Square::B1, Square::C3, None));
+        let second = ChessBoard::default()
+            .play_move(Move::new(Square::B1, Square::C3, None))
+            .play_move(Move::new(Square::G8, Square::F6, None))
+            .play_move(Move::new(Square::G1, Square::F3, None));
+        assert_eq!(first, second);
+        assert_eq!(hash(&first), hash(&second));
+
+        let mut map = HashMap::new();
+        map.insert(first, 42);
+        map.insert(ChessBoard::default(), 0);
+        assert_eq!(map.get(&second), Some(&42));
+        assert_eq!(map.get(&ChessBoard::default()), Some(&0));
+        assert_eq!(map.get(&ChessBoard::kiwipete()), None);
+    }
+
     #[test]
     fn invalid_incoherent_plie_count() {
         let position = {
@@ -584,6 +1172,20 @@ mod test {
         assert_eq!(res.err().unwrap(), ValidationError::InvalidCastlingRights);
     }
 
+    #[test]
+    fn invalid_castling_rights_king_file() {
+        let res = {
+            let mut builder = ChessBoardBuilder::new();
+            builder[Square::F1] = Some((Piece::King, Color::White));
+            builder[Square::H1] = Some((Piece::Rook, Color::White));
+            builder[Square::E8] = Some((Piece::King, Color::Black));
+            builder.with_castle_rights(CastleRights::KingSide, Color::White);
+            builder.with_castling_files(CastlingFiles::STANDARD.with_king(File::G), Color::White);
+            TryInto::<ChessBoard>::try_into(builder)
+        };
+        assert_eq!(res.err().unwrap(), ValidationError::InvalidCastlingRights);
+    }
+
     #[test]
     fn invalid_castling_rights_moved_king() {
         let res = {
@@ -702,6 +1304,92 @@ mod test {
         assert_eq!(res.err().unwrap(), ValidationError::TooManyPieces);
     }
 
+    #[test]
+    fn validate_all_reports_every_error() {
+        let position = {
+            let mut builder = ChessBoardBuilder::new();
+            builder[Square::E1] = Some((Piece::King, Color::White));
+            builder[Square::E8] = Some((Piece::King, Color::Black));
+            let mut board: ChessBoard = builder.try_into().unwrap();
+            board.xor(Color::White, Piece::King, Square::A1);
+            board.xor(Color::White, Piece::Queen, Square::E7);
+            board
+        };
+        assert_eq!(
+            position.validate_all(),
+            vec![
+                ValidationError::TooManyPieces,
+                ValidationError::MissingKing,
+                ValidationError::OpponentInCheck,
+            ]
+        );
+        assert_eq!(
+            position.validate().err().unwrap(),
+            ValidationError::TooManyPieces
+        );
+    }
+
+    #[test]
+    fn validate_all_reports_repeated_errors() {
+        let position = {
+            let mut builder = ChessBoardBuilder::new();
+            builder[Square::E1] = Some((Piece::King, Color::White));
+            builder[Square::E8] = Some((Piece::King, Color::Black));
+            let mut board: ChessBoard = builder.try_into().unwrap();
+            board.xor(Color::White, Piece::King, Square::E1);
+            board.xor(Color::Black, Piece::King, Square::E8);
+            board
+        };
+        assert_eq!(
+            position.validate_all(),
+            vec![ValidationError::MissingKing, ValidationError::MissingKing]
+        );
+        assert_eq!(
+            position.validate().err().unwrap(),
+            ValidationError::MissingKing
+        );
+    }
+
+    #[test]
+    fn validate_all_valid() {
+        assert!(ChessBoard::default().validate_all().is_empty());
+    }
+
+    #[test]
+    fn normalized() {
+        // No black pawn next to the pushed pawn
+        let position = ChessBoard::default().play_move(Move::new(Square::E2, Square::E4, None));
+        let expected =
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1")
+                .unwrap();
+        assert_ne!(position, expected);
+        assert_eq!(position.zobrist_hash(), expected.zobrist_hash());
+        let normalized = position.normalized();
+        assert_eq!(normalized.en_passant(), None);
+        assert_eq!(normalized, expected);
+        assert_eq!(normalized.zobrist_hash(), expected.zobrist_hash());
+        assert_eq!(normalized.zobrist_hash(), normalized.zobrist_from_scratch());
+
+        // The en-passant capture is possible
+        let position = ChessBoard::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(position.normalized(), position);
+    }
+
+    #[test]
+    fn en_passant_fen() {
+        // No black pawn next to the pushed pawn
+        let position = ChessBoard::default().play_move(Move::new(Square::E2, Square::E4, None));
+        assert_eq!(position.en_passant_fen(false), Some(Square::E3));
+        assert_eq!(position.en_passant_fen(true), None);
+
+        let position = ChessBoard::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(position.en_passant_fen(false), Some(Square::E3));
+        assert_eq!(position.en_passant_fen(true), Some(Square::E3));
+
+        assert_eq!(ChessBoard::default().en_passant_fen(false), None);
+        assert_eq!(ChessBoard::default().en_passant_fen(true), None);
+    }
+
     #[test]
     fn checkers() {
         let position = {
@@ -724,26 +1412,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn checkers_cached() {
+        let mut position = ChessBoard::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert_eq!(position.checkers(), Bitboard::EMPTY);
+        assert_eq!(position.checkers(), Bitboard::EMPTY);
+
+        let chess_move = Move::new(Square::A1, Square::A8, None);
+        let state = position.play_move_inplace(chess_move);
+        assert_eq!(position.checkers(), Square::A8.into_bitboard());
+        assert_eq!(position.checkers(), Square::A8.into_bitboard());
+        assert_eq!(position.clone().checkers(), Square::A8.into_bitboard());
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("R3k3/8/8/8/8/8/8/4K3 b - - 1 1").unwrap()
+        );
+
+        position.unplay_move(chess_move, state);
+        assert_eq!(position.checkers(), Bitboard::EMPTY);
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap()
+        );
+    }
+
+    #[test]
+    fn checkers_pawn_on_back_rank() {
+        let position = ChessBoard::from_fen("4k3/3P4/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert_eq!(position.checkers(), Square::D7.into_bitboard());
+        let position = ChessBoard::from_fen("4k3/8/8/8/8/8/5p2/4K3 w - - 0 1").unwrap();
+        assert_eq!(position.checkers(), Square::F2.into_bitboard());
+    }
+
     #[test]
     fn play_move() {
         // Start from default position
         let mut position = ChessBoard::default();
         // Modify it to account for e4 move
-        position.play_move_inplace(Move::new(Square::E2, Square::E4, None));
+        let _ = position.play_move_inplace(Move::new(Square::E2, Square::E4, None));
         assert_eq!(
             position,
             ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
                 .unwrap()
         );
         // And now c5
-        position.play_move_inplace(Move::new(Square::C7, Square::C5, None));
+        let _ = position.play_move_inplace(Move::new(Square::C7, Square::C5, None));
         assert_eq!(
             position,
             ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2")
                 .unwrap()
         );
         // Finally, Nf3
-        position.play_move_inplace(Move::new(Square::G1, Square::F3, None));
+        let _ = position.play_move_inplace(Move::new(Square::G1, Square::F3, None));
         assert_eq!(
             position,
             ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2 ")
@@ -758,7 +1478,7 @@ mod test {
 
         let capture = Move::new(Square::H1, Square::H8, None);
 
-        position.play_move_inplace(capture);
+        let _ = position.play_move_inplace(capture);
         assert_eq!(position, expected);
     }
 
@@ -796,6 +1516,147 @@ mod test {
         );
     }
 
+    #[test]
+    fn try_play_move() {
+        let position = ChessBoard::from_fen("4k3/1P6/8/8/8/8/8/4K1N1 w - - 0 1").unwrap();
+        assert_eq!(
+            position.try_play_move(Move::new(Square::G1, Square::F3, Some(Piece::Queen))),
+            Err(MoveError::InvalidPromotion)
+        );
+        assert_eq!(
+            position.try_play_move(Move::new(Square::B7, Square::B8, Some(Piece::King))),
+            Err(MoveError::InvalidPromotion)
+        );
+        assert_eq!(
+            position.try_play_move(Move::new(Square::B7, Square::B8, None)),
+            Err(MoveError::MissingPromotion)
+        );
+        assert_eq!(
+            position.try_play_move(Move::new(Square::E8, Square::E7, None)),
+            Err(MoveError::NoPieceToMove)
+        );
+        assert_eq!(
+            position.try_play_move(Move::new(Square::B7, Square::B8, Some(Piece::Knight))),
+            Ok(position.play_move(Move::new(Square::B7, Square::B8, Some(Piece::Knight))))
+        );
+        assert_eq!(
+            position.try_play_move(Move::new(Square::G1, Square::F3, None)),
+            Ok(position.play_move(Move::new(Square::G1, Square::F3, None)))
+        );
+    }
+
+    #[test]
+    fn play_move_castling() {
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let chess_move = Move::new(Square::E1, Square::G1, None);
+        let (mut played, state) = position.play_move_with_undo(chess_move);
+        assert_eq!(
+            played,
+            ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1").unwrap()
+        );
+        played.unplay_move(chess_move, state);
+        assert_eq!(played, position);
+
+        let position = position.play_move(Move::new(Square::A1, Square::A2, None));
+        let chess_move = Move::new(Square::E8, Square::C8, None);
+        let (mut played, state) = position.play_move_with_undo(chess_move);
+        assert_eq!(
+            played,
+            ChessBoard::from_fen("2kr3r/8/8/8/8/8/R7/4K2R w K - 2 2").unwrap()
+        );
+        played.unplay_move(chess_move, state);
+        assert_eq!(played, position);
+    }
+
+    #[test]
+    fn play_move_castling_non_standard_rooks() {
+        for (fen, chess_move, expected) in [
+            (
+                "r3k2r/8/8/8/8/8/8/1R2K2R w HBha - 0 1",
+                Move::new(Square::E1, Square::C1, None),
+                "r3k2r/8/8/8/8/8/8/2KR3R b ha - 1 1",
+            ),
+            // The king lands on the rook's original square
+            (
+                "4k3/8/8/8/8/8/8/4K1R1 w G - 0 1",
+                Move::new(Square::E1, Square::G1, None),
+                "4k3/8/8/8/8/8/8/5RK1 b - - 1 1",
+            ),
+            // The rook does not move
+            (
+                "4k3/8/8/8/8/8/8/3RK3 w D - 0 1",
+                Move::new(Square::E1, Square::C1, None),
+                "4k3/8/8/8/8/8/8/2KR4 b - - 1 1",
+            ),
+            // Outside of the E file, the king castles by moving onto its rook
+            (
+                "1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1",
+                Move::new(Square::G1, Square::H1, None),
+                "1r4kr/8/8/8/8/8/8/1R3RK1 b hb - 1 1",
+            ),
+            (
+                "1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1",
+                Move::new(Square::G1, Square::B1, None),
+                "1r4kr/8/8/8/8/8/8/2KR3R b hb - 1 1",
+            ),
+            (
+                "4k3/8/8/8/8/8/8/5K1R w H - 0 1",
+                Move::new(Square::F1, Square::H1, None),
+                "4k3/8/8/8/8/8/8/5RK1 b - - 1 1",
+            ),
+            (
+                "4k3/8/8/8/8/8/8/RK6 w A - 0 1",
+                Move::new(Square::B1, Square::A1, None),
+                "4k3/8/8/8/8/8/8/2KR4 b - - 1 1",
+            ),
+        ] {
+            let position = ChessBoard::from_fen(fen).unwrap();
+            assert!(position.is_legal(chess_move), "{}", fen);
+            let (mut played, state) = position.play_move_with_undo(chess_move);
+            assert_eq!(played, ChessBoard::from_fen(expected).unwrap(), "{}", fen);
+            assert_eq!(played.zobrist_hash(), played.zobrist_from_scratch());
+            assert_eq!(played.zobrist_hash(), position.hash_after(chess_move));
+            played.unplay_move(chess_move, state);
+            assert_eq!(played, position, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn play_move_non_standard_rook_loses_castling() {
+        let position = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/RR2K2R w HBha - 0 1").unwrap();
+        // Only moving the castling rook loses the rights
+        let played = position.play_move(Move::new(Square::A1, Square::A2, None));
+        assert_eq!(played.castle_rights(Color::White), CastleRights::BothSides);
+        let played = position.play_move(Move::new(Square::B1, Square::B2, None));
+        assert_eq!(
+            played,
+            ChessBoard::from_fen("r3k2r/8/8/8/8/8/1R6/R3K2R b Hha - 1 1").unwrap()
+        );
+        assert_eq!(played.castling_files(Color::White), CastlingFiles::STANDARD);
+        assert_eq!(played.zobrist_hash(), played.zobrist_from_scratch());
+    }
+
+    #[test]
+    fn play_move_rook_off_corner_keeps_castling() {
+        let position = ChessBoard::from_fen("4k3/8/8/8/R7/8/8/4K2R w K - 0 1").unwrap();
+        let played = position.play_move(Move::new(Square::A4, Square::A5, None));
+        assert_eq!(played.castle_rights(Color::White), CastleRights::KingSide);
+    }
+
+    #[test]
+    fn play_move_with_undo() {
+        let original = ChessBoard::from_fen("3q3k/8/8/8/8/8/8/K2Q4 w - - 0 1").unwrap();
+        let expected = ChessBoard::from_fen("3Q3k/8/8/8/8/8/8/K7 b - - 0 1").unwrap();
+
+        let capture = Move::new(Square::D1, Square::D8, None);
+
+        let (mut position, state) = original.play_move_with_undo(capture);
+        assert_eq!(position, expected);
+
+        position.unplay_move(capture, state);
+        assert_eq!(position, original);
+    }
+
     #[test]
     fn play_move_undo_capture() {
         let mut position = ChessBoard::from_fen("3q3k/8/8/8/8/8/8/K2Q4 w - - 0 1").unwrap();
@@ -811,6 +1672,21 @@ mod test {
         assert_eq!(position, original);
     }
 
+    #[test]
+    fn play_move_undo_en_passant() {
+        let mut position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let expected = ChessBoard::from_fen("4k3/8/3P4/8/8/8/8/4K3 b - - 0 1").unwrap();
+        let original = position.clone();
+
+        let en_passant = Move::new(Square::E5, Square::D6, None);
+
+        let state = position.play_move_inplace(en_passant);
+        assert_eq!(position, expected);
+
+        position.unplay_move(en_passant, state);
+        assert_eq!(position, original);
+    }
+
     #[test]
     fn play_move_undo_promotion() {
         let mut position = ChessBoard::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
@@ -0,0 +1,104 @@
+use super::ChessBoard;
+use crate::board::{Move, Piece};
+
+/// The kind of a [Move], as played in a given position, see [ChessBoard::classify_move].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoveKind {
+    /// A quiet move, which does not fit any other kind.
+    Normal,
+    /// A capture of the piece standing on the destination square.
+    Capture,
+    /// A pawn capturing en-passant.
+    EnPassant,
+    /// A king castling, the rook is moved alongside it.
+    Castle,
+    /// A pawn promoting, whether it captures or not.
+    Promotion,
+    /// A pawn moving two squares forward from its starting rank.
+    DoublePush,
+}
+
+impl ChessBoard {
+    /// Classify the given [Move], which must start from one of the current player's pieces. The
+    /// move is assumed to be pseudo-legal, e.g: one returned by [ChessBoard::legal_moves].
+    pub fn classify_move(&self, chess_move: Move) -> MoveKind {
+        // Unwrap is fine, the move must start from an occupied square
+        let move_piece = self.piece_on(chess_move.start()).unwrap();
+        let is_capture = self
+            .color_occupancy(!self.current_player())
+            .contains(chess_move.destination());
+
+        if chess_move.promotion().is_some() {
+            MoveKind::Promotion
+        } else if self
+            .castling_squares(self.current_player(), chess_move)
+            .is_some()
+        {
+            MoveKind::Castle
+        } else if is_capture {
+            MoveKind::Capture
+        } else if move_piece == Piece::Pawn && Some(chess_move.destination()) == self.en_passant() {
+            MoveKind::EnPassant
+        } else if self.en_passant_after(chess_move, move_piece).is_some() {
+            MoveKind::DoublePush
+        } else {
+            MoveKind::Normal
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn classify_move() {
+        let position = ChessBoard::kiwipete();
+        for (chess_move, kind) in [
+            (Move::new(Square::A2, Square::A3, None), MoveKind::Normal),
+            (Move::new(Square::F3, Square::F5, None), MoveKind::Normal),
+            (
+                Move::new(Square::A2, Square::A4, None),
+                MoveKind::DoublePush,
+            ),
+            (Move::new(Square::E5, Square::F7, None), MoveKind::Capture),
+            (Move::new(Square::D5, Square::E6, None), MoveKind::Capture),
+            (Move::new(Square::E1, Square::G1, None), MoveKind::Castle),
+            (Move::new(Square::E1, Square::C1, None), MoveKind::Castle),
+            (Move::new(Square::E1, Square::D1, None), MoveKind::Normal),
+        ] {
+            assert_eq!(position.classify_move(chess_move), kind, "{:?}", chess_move);
+        }
+    }
+
+    #[test]
+    fn classify_en_passant() {
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(
+            position.classify_move(Move::new(Square::E5, Square::D6, None)),
+            MoveKind::EnPassant
+        );
+        assert_eq!(
+            position.classify_move(Move::new(Square::E5, Square::E6, None)),
+            MoveKind::Normal
+        );
+    }
+
+    #[test]
+    fn classify_promotion() {
+        let position = ChessBoard::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        for chess_move in [
+            Move::new(Square::A7, Square::A8, Some(Piece::Queen)),
+            Move::new(Square::A7, Square::B8, Some(Piece::Knight)),
+        ] {
+            assert_eq!(
+                position.classify_move(chess_move),
+                MoveKind::Promotion,
+                "{:?}",
+                chess_move
+            );
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use alloc::{vec, vec::Vec};
+
+use super::{ChessBoard, MoveError, NonReversibleState, SanError};
+use crate::board::{File, Move, Piece, Rank, Square};
+
+/// The components of a move written in Standard Algebraic Notation.
+enum SanMove {
+    /// Castling, either king-side or queen-side.
+    Castle { king_side: bool },
+    /// Any other move, with optional disambiguation of its start [Square].
+    Normal {
+        piece: Piece,
+        file: Option<File>,
+        rank: Option<Rank>,
+        destination: Square,
+        promotion: Option<Piece>,
+    },
+}
+
+/// Parse a piece letter, as used in SAN for pieces other than pawns.
+fn parse_piece(b: u8) -> Option<Piece> {
+    let res = match b {
+        b'K' => Piece::King,
+        b'Q' => Piece::Queen,
+        b'R' => Piece::Rook,
+        b'B' => Piece::Bishop,
+        b'N' => Piece::Knight,
+        _ => return None,
+    };
+    Some(res)
+}
+
+/// Parse a [File] from its lower-case letter.
+fn parse_file(b: u8) -> Option<File> {
+    matches!(b, b'a'..=b'h').then(|| File::from_index((b - b'a') as usize))
+}
+
+/// Parse a [Rank] from its digit.
+fn parse_rank(b: u8) -> Option<Rank> {
+    matches!(b, b'1'..=b'8').then(|| Rank::from_index((b - b'1') as usize))
+}
+
+impl SanMove {
+    /// Parse the input without looking at any position, check and annotation suffixes are
+    /// ignored.
+    fn parse(s: &str) -> Result<Self, SanError> {
+        let s = s.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        match s {
+            "O-O" | "0-0" => return Ok(Self::Castle { king_side: true }),
+            "O-O-O" | "0-0-0" => return Ok(Self::Castle { king_side: false }),
+            _ => {}
+        }
+
+        let mut bytes = s.as_bytes();
+
+        let piece = match bytes.first().copied().and_then(parse_piece) {
+            Some(piece) => {
+                bytes = &bytes[1..];
+                piece
+            }
+            None => Piece::Pawn,
+        };
+
+        let promotion = match bytes {
+            [rest @ .., b'=', promotion] => {
+                bytes = rest;
+                Some(*promotion)
+            }
+            [rest @ .., promotion] if piece == Piece::Pawn && parse_piece(*promotion).is_some() => {
+                bytes = rest;
+                Some(*promotion)
+            }
+            _ => None,
+        };
+        let promotion = match promotion.map(parse_piece) {
+            None => None,
+            Some(Some(Piece::King)) | Some(None) => return Err(SanError::ParseError),
+            Some(promotion) => promotion,
+        };
+
+        let (rest, destination) = match bytes {
+            [rest @ .., file, rank] => {
+                let file = parse_file(*file).ok_or(SanError::ParseError)?;
+                let rank = parse_rank(*rank).ok_or(SanError::ParseError)?;
+                (rest, Square::new(file, rank))
+            }
+            _ => return Err(SanError::ParseError),
+        };
+
+        // Disambiguation and capture marker, in this order
+        let rest = rest.strip_suffix(b"x").unwrap_or(rest);
+        let (file, rank) = match rest {
+            [] => (None, None),
+            [b] if parse_file(*b).is_some() => (parse_file(*b), None),
+            [b] if parse_rank(*b).is_some() => (None, parse_rank(*b)),
+            [file, rank] => (
+                Some(parse_file(*file).ok_or(SanError::ParseError)?),
+                Some(parse_rank(*rank).ok_or(SanError::ParseError)?),
+            ),
+            _ => return Err(SanError::ParseError),
+        };
+
+        Ok(Self::Normal {
+            piece,
+            file,
+            rank,
+            destination,
+            promotion,
+        })
+    }
+}
+
+impl ChessBoard {
+    /// Parse a [Move] written in Standard Algebraic Notation, e.g: `Nf3`, `exd5`, `e8=Q`, or
+    /// `O-O`. Check, checkmate, and annotation suffixes are accepted but ignored. Return
+    /// Err([SanError]) if the input is not valid, or does not match exactly one legal move.
+    pub fn parse_san(&self, s: &str) -> Result<Move, SanError> {
+        let color = self.current_player();
+
+        let candidates: Vec<Move> = match SanMove::parse(s)? {
+            SanMove::Castle { king_side } => vec![self.castling_move(color, king_side)],
+            SanMove::Normal {
+                piece,
+                file,
+                rank,
+                destination,
+                promotion,
+            } => self
+                .occupancy(piece, color)
+                .into_iter()
+                .filter(|start| file.is_none_or(|file| start.file() == file))
+                .filter(|start| rank.is_none_or(|rank| start.rank() == rank))
+                .map(|start| Move::new(start, destination, promotion))
+                .collect(),
+        };
+
+        let candidates: Vec<Move> = candidates
+            .into_iter()
+            .filter(|&chess_move| self.is_pseudo_legal(chess_move))
+            .collect();
+        if candidates.is_empty() {
+            return Err(SanError::NoSuchPiece);
+        }
+
+        let mut legal = candidates
+            .into_iter()
+            .filter(|&chess_move| self.is_legal(chess_move));
+        match (legal.next(), legal.next()) {
+            (None, _) => Err(SanError::Illegal),
+            (Some(chess_move), None) => Ok(chess_move),
+            (Some(_), Some(_)) => Err(SanError::Ambiguous),
+        }
+    }
+
+    /// Parse a [Move] written in Standard Algebraic Notation, and play it in place, see
+    /// [ChessBoard::parse_san]. Return the [NonReversibleState] needed to undo it.
+    pub fn play_san(&mut self, s: &str) -> Result<NonReversibleState, SanError> {
+        let chess_move = self.parse_san(s)?;
+        Ok(self.play_move_inplace(chess_move))
+    }
+
+    /// Parse a [Move] written in Standard Algebraic Notation, and play it in place, see
+    /// [ChessBoard::parse_san]. Return the [Move] that was played, or Err([SanError]) explaining
+    /// why the input was rejected, e.g: to give feedback to a user, in which case the board is
+    /// left untouched.
+    pub fn make_move_san(&mut self, s: &str) -> Result<Move, SanError> {
+        let chess_move = self.parse_san(s)?;
+        // The undo state is not needed, the move is returned instead
+        let _ = self.play_move_inplace(chess_move);
+        Ok(chess_move)
+    }
+
+    /// Parse a [Move] written in coordinate notation, e.g: `e2e4` or `e7e8q`, and play it in
+    /// place if it is legal. Return the [NonReversibleState] needed to undo it.
+    pub fn play_uci(&mut self, s: &str) -> Result<NonReversibleState, MoveError> {
+        let chess_move = Move::try_from(s)?;
+        if !self.legal_moves().contains(&chess_move) {
+            return Err(MoveError::IllegalMove);
+        }
+        Ok(self.play_move_inplace(chess_move))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn play_san() {
+        let mut position = ChessBoard::default();
+        position.play_san("e4").unwrap();
+        position.play_san("c5").unwrap();
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2")
+                .unwrap()
+        );
+        position.play_san("Nf3").unwrap();
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn play_san_undo() {
+        let mut position = ChessBoard::default();
+        let state = position.play_san("Nc3").unwrap();
+        position.unplay_move(Move::new(Square::B1, Square::C3, None), state);
+        assert_eq!(position, ChessBoard::default());
+    }
+
+    #[test]
+    fn make_move_san() {
+        let mut position = ChessBoard::from_fen("4k3/8/8/8/8/1N6/8/4KN2 w - - 0 1").unwrap();
+        let original = position.clone();
+        // Both knights can reach d2
+        assert_eq!(position.make_move_san("Nd2"), Err(SanError::Ambiguous));
+        assert_eq!(position.make_move_san("Nh5?"), Err(SanError::NoSuchPiece));
+        assert_eq!(position.make_move_san("Nd"), Err(SanError::ParseError));
+        assert_eq!(position, original);
+
+        assert_eq!(
+            position.make_move_san("Nbd2"),
+            Ok(Move::new(Square::B3, Square::D2, None))
+        );
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("4k3/8/8/8/8/8/3N4/4KN2 b - - 1 1").unwrap()
+        );
+        // The king would be left in check
+        let mut position = ChessBoard::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        assert_eq!(position.make_move_san("Nc3"), Err(SanError::Illegal));
+    }
+
+    #[test]
+    fn parse_san() {
+        let position = ChessBoard::kiwipete();
+        for (san, expected) in [
+            ("O-O", Move::new(Square::E1, Square::G1, None)),
+            ("0-0-0", Move::new(Square::E1, Square::C1, None)),
+            ("Nxf7", Move::new(Square::E5, Square::F7, None)),
+            ("Bxa6", Move::new(Square::E2, Square::A6, None)),
+            ("dxe6", Move::new(Square::D5, Square::E6, None)),
+            ("d6", Move::new(Square::D5, Square::D6, None)),
+            ("gxh3+", Move::new(Square::G2, Square::H3, None)),
+            ("Qxf6!?", Move::new(Square::F3, Square::F6, None)),
+            ("Ncb1", Move::new(Square::C3, Square::B1, None)),
+            ("Ned3", Move::new(Square::E5, Square::D3, None)),
+        ] {
+            assert_eq!(position.parse_san(san), Ok(expected), "{}", san);
+        }
+    }
+
+    #[test]
+    fn parse_san_promotion() {
+        let position = ChessBoard::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        for (san, expected) in [
+            (
+                "axb8=Q",
+                Move::new(Square::A7, Square::B8, Some(Piece::Queen)),
+            ),
+            (
+                "axb8N",
+                Move::new(Square::A7, Square::B8, Some(Piece::Knight)),
+            ),
+            ("a8=R", Move::new(Square::A7, Square::A8, Some(Piece::Rook))),
+        ] {
+            assert_eq!(position.parse_san(san), Ok(expected), "{}", san);
+        }
+        assert_eq!(position.parse_san("a8=K"), Err(SanError::ParseError));
+        assert_eq!(position.parse_san("a8"), Err(SanError::NoSuchPiece));
+    }
+
+    #[test]
+    fn parse_san_errors() {
+        let position = ChessBoard::kiwipete();
+        // No rook can reach e4
+        assert_eq!(position.parse_san("Re4"), Err(SanError::NoSuchPiece));
+        for san in ["", "Nf", "Zf3", "Nf9", "N1xx3", "O-O-O-O"] {
+            assert_eq!(
+                position.parse_san(san),
+                Err(SanError::ParseError),
+                "{}",
+                san
+            );
+        }
+
+        let position = ChessBoard::from_fen("4k3/4r3/8/8/8/1N6/4B3/1N2KN2 w - - 0 1").unwrap();
+        // All knights can reach d2
+        assert_eq!(position.parse_san("Nd2"), Err(SanError::Ambiguous));
+        assert_eq!(position.parse_san("Nbd2"), Err(SanError::Ambiguous));
+        assert_eq!(position.parse_san("N1d2"), Err(SanError::Ambiguous));
+        assert_eq!(
+            position.parse_san("Nb1d2"),
+            Ok(Move::new(Square::B1, Square::D2, None))
+        );
+        assert_eq!(
+            position.parse_san("Nfd2"),
+            Ok(Move::new(Square::F1, Square::D2, None))
+        );
+        assert_eq!(
+            position.parse_san("N3d2"),
+            Ok(Move::new(Square::B3, Square::D2, None))
+        );
+        // The bishop is pinned
+        assert_eq!(position.parse_san("Bd3"), Err(SanError::Illegal));
+    }
+
+    #[test]
+    fn play_uci() {
+        let mut position = ChessBoard::default();
+        position.play_uci("e2e4").unwrap();
+        assert_eq!(
+            position,
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .unwrap()
+        );
+        assert_eq!(position.play_uci("e2e4"), Err(MoveError::IllegalMove));
+        assert_eq!(
+            position.play_uci("e7"),
+            Err(MoveError::InvalidNotation(
+                crate::board::MoveParseError::InvalidLength
+            ))
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use super::see::see_value;
+use super::ChessBoard;
+use crate::board::{Move, Piece};
+use crate::eval::PieceValues;
+use crate::movegen::MoveList;
+
+impl ChessBoard {
+    /// Return the [Piece] captured by the given [Move], accounting for en-passant, or `None` if it
+    /// is not a capture.
+    pub(crate) fn captured_piece(&self, chess_move: Move) -> Option<Piece> {
+        let destination = chess_move.destination();
+        if self
+            .color_occupancy(!self.current_player())
+            .contains(destination)
+        {
+            return self.piece_on(destination);
+        }
+        let is_en_passant = Some(destination) == self.en_passant()
+            && self.piece_on(chess_move.start()) == Some(Piece::Pawn);
+        is_en_passant.then_some(Piece::Pawn)
+    }
+
+    /// Return the value, in centipawns, of the [Piece] captured by the given [Move] according to
+    /// [PieceValues::DEFAULT], accounting for en-passant, or 0 if it is not a capture. Useful for
+    /// quick evaluation deltas, e.g: for futility pruning.
+    pub fn captured_value(&self, chess_move: Move) -> i32 {
+        self.captured_piece(chess_move)
+            .map_or(0, |piece| PieceValues::DEFAULT.value(piece))
+    }
+
+    /// Compute the Most-Valuable-Victim/Least-Valuable-Attacker key of a [Move]: captures of more
+    /// valuable pieces sort first, ties are broken by preferring the least valuable attacker.
+    /// Non-capturing moves sort after all captures.
+    pub fn mvv_lva(&self, chess_move: Move) -> (i32, i32) {
+        let Some(victim) = self.captured_piece(chess_move) else {
+            return (0, 0);
+        };
+        let attacker = self.piece_on(chess_move.start()).map_or(0, see_value);
+        (-see_value(victim), attacker)
+    }
+
+    /// Sort the given [MoveList] in Most-Valuable-Victim/Least-Valuable-Attacker order, see
+    /// [ChessBoard::mvv_lva]. The sort is stable, non-capturing moves keep their relative order at
+    /// the end of the list.
+    pub fn order_captures(&self, moves: &mut MoveList) {
+        moves.sort_by_key(|&chess_move| self.mvv_lva(chess_move));
+    }
+
+    /// Compute the default ordering key of a [Move], see [ChessBoard::order_moves].
+    fn ordering_key(&self, chess_move: Move) -> (u8, (i32, i32)) {
+        let is_capture = self.captured_piece(chess_move).is_some();
+        let tier = match chess_move.promotion() {
+            Some(piece) if piece != Piece::Queen => 4,
+            _ if is_capture && self.see_ge(chess_move, 0) => 0,
+            Some(_) => 1,
+            _ if !is_capture => 2,
+            _ => 3,
+        };
+        (tier, self.mvv_lva(chess_move))
+    }
+
+    /// Sort the given [MoveList] in a sensible default order for searching: non-losing captures
+    /// in Most-Valuable-Victim/Least-Valuable-Attacker order, queen promotions, quiet moves,
+    /// losing captures, and finally under-promotions, which are rarely the best move. The sort is
+    /// stable, moves of the same kind keep their relative order.
+    pub fn order_moves(&self, moves: &mut MoveList) {
+        moves.sort_by_key(|&chess_move| self.ordering_key(chess_move));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn captured_value() {
+        let position = ChessBoard::from_fen("4k3/8/3r4/2P1p3/3Q4/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            position.captured_value(Move::new(Square::D4, Square::D6, None)),
+            PieceValues::DEFAULT.value(Piece::Rook)
+        );
+        assert_eq!(
+            position.captured_value(Move::new(Square::D4, Square::E5, None)),
+            PieceValues::DEFAULT.value(Piece::Pawn)
+        );
+        assert_eq!(
+            position.captured_value(Move::new(Square::D4, Square::D5, None)),
+            0
+        );
+
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(
+            position.captured_value(Move::new(Square::E5, Square::D6, None)),
+            PieceValues::DEFAULT.value(Piece::Pawn)
+        );
+    }
+
+    #[test]
+    fn mvv_lva() {
+        let position = ChessBoard::from_fen("4k3/8/3q4/2P1p3/3Q4/8/8/4K3 w - - 0 1").unwrap();
+        let pawn_takes_queen = Move::new(Square::C5, Square::D6, None);
+        let queen_takes_queen = Move::new(Square::D4, Square::D6, None);
+        let queen_takes_pawn = Move::new(Square::D4, Square::E5, None);
+        let quiet = Move::new(Square::D4, Square::D5, None);
+        assert!(position.mvv_lva(pawn_takes_queen) < position.mvv_lva(queen_takes_queen));
+        assert!(position.mvv_lva(queen_takes_queen) < position.mvv_lva(queen_takes_pawn));
+        assert!(position.mvv_lva(queen_takes_pawn) < position.mvv_lva(quiet));
+    }
+
+    #[test]
+    fn order_captures() {
+        let position = ChessBoard::from_fen("4k3/8/3q4/2P1p3/3Q4/8/8/4K3 w - - 0 1").unwrap();
+        let mut moves = position.legal_moves();
+        position.order_captures(&mut moves);
+        assert_eq!(
+            moves[..3],
+            [
+                Move::new(Square::C5, Square::D6, None),
+                Move::new(Square::D4, Square::D6, None),
+                Move::new(Square::D4, Square::E5, None),
+            ]
+        );
+        let quiets = position
+            .legal_moves()
+            .iter()
+            .copied()
+            .filter(|&m| position.captured_piece(m).is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(moves[3..], quiets);
+    }
+
+    #[test]
+    fn order_moves() {
+        let position = ChessBoard::from_fen("1n2k3/P7/4p3/3p4/2P3r1/5B2/8/4K3 w - - 0 1").unwrap();
+        let mut moves = position.legal_moves();
+        position.order_moves(&mut moves);
+        assert_eq!(
+            moves[..4],
+            [
+                // Wins a rook
+                Move::new(Square::F3, Square::G4, None),
+                // Wins a knight, capturing with a pawn
+                Move::new(Square::A7, Square::B8, Some(Piece::Queen)),
+                // Trades pawns
+                Move::new(Square::C4, Square::D5, None),
+                Move::new(Square::A7, Square::A8, Some(Piece::Queen)),
+            ]
+        );
+        // The bishop would be recaptured by a pawn
+        let index = |chess_move| moves.iter().position(|&m| m == chess_move).unwrap();
+        let losing_capture = index(Move::new(Square::F3, Square::D5, None));
+        let quiet = index(Move::new(Square::E1, Square::D1, None));
+        assert!(quiet < losing_capture);
+        let under_promotion = index(Move::new(Square::A7, Square::A8, Some(Piece::Knight)));
+        assert!(losing_capture < under_promotion);
+        assert!(index(Move::new(Square::A7, Square::A8, Some(Piece::Queen))) < under_promotion);
+    }
+
+    #[test]
+    fn order_captures_en_passant() {
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let mut moves = position.legal_moves();
+        position.order_captures(&mut moves);
+        assert_eq!(moves[0], Move::new(Square::E5, Square::D6, None));
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use super::ChessBoard;
+
+impl ChessBoard {
+    /// Count the number of leaf nodes of the legal move tree, down to the given depth. This is
+    /// used to check the correctness of move generation against known results.
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut position = self.clone();
+        let mut res = 0;
+        for &chess_move in &moves {
+            let state = position.play_move_inplace(chess_move);
+            res += position.perft(depth - 1);
+            position.unplay_move(chess_move, state);
+        }
+        res
+    }
+
+    /// Count the number of leaf nodes of the legal move tree, down to the given depth, like
+    /// [ChessBoard::perft]. The count of each sub-tree is stored in `cache`, keyed by its Zobrist
+    /// hash and depth, and re-used when encountering transpositions. The cache can be kept across
+    /// calls to speed-up repeated runs.
+    #[cfg(feature = "std")]
+    pub fn perft_hashed(&self, depth: u32, cache: &mut HashMap<(u64, u32), u64>) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        if let Some(&res) = cache.get(&(self.zobrist_hash(), depth)) {
+            return res;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut position = self.clone();
+        let mut res = 0;
+        for &chess_move in &moves {
+            let state = position.play_move_inplace(chess_move);
+            res += position.perft_hashed(depth - 1, cache);
+            position.unplay_move(chess_move, state);
+        }
+        cache.insert((self.zobrist_hash(), depth), res);
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn perft() {
+        assert_eq!(ChessBoard::default().perft(0), 1);
+        for (position, expected) in ChessBoard::test_positions().into_iter().zip([
+            [20, 400, 8902],
+            [48, 2039, 97862],
+            [14, 191, 2812],
+            [6, 264, 9467],
+            [44, 1486, 62379],
+        ]) {
+            for (depth, expected) in (1..).zip(expected) {
+                assert_eq!(position.perft(depth), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn perft_chess960() {
+        let position = ChessBoard::from_fen(
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        )
+        .unwrap();
+        for (depth, expected) in (1..).zip([21, 528, 12189, 326672]) {
+            assert_eq!(position.perft(depth), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn perft_hashed() {
+        let position = ChessBoard::kiwipete();
+        let mut cache = HashMap::new();
+        for depth in 1..=4 {
+            assert_eq!(
+                position.perft_hashed(depth, &mut cache),
+                position.perft(depth)
+            );
+        }
+        assert!(!cache.is_empty());
+        // Re-use the populated cache
+        assert_eq!(position.perft_hashed(3, &mut cache), 97862);
+        assert_eq!(position.perft_hashed(4, &mut cache), 4085603);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[ignore = "slow, run it in release mode"]
+    fn perft_hashed_deep() {
+        let position = ChessBoard::kiwipete();
+        let mut cache = HashMap::new();
+        assert_eq!(position.perft_hashed(5, &mut cache), 193690690);
+    }
+}
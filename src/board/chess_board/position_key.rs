@@ -0,0 +1,65 @@
+use super::ChessBoard;
+use crate::board::{Bitboard, CastleRights, CastlingFiles, Color, Piece, Square};
+
+/// A key identifying a position, built from the fields of a [ChessBoard] which define it: its
+/// pieces, castling rights, en-passant target, and current player. Move counters are ignored, so
+/// that repeated positions compare equal.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PositionKey {
+    piece_occupancy: [Bitboard; Piece::NUM_VARIANTS],
+    color_occupancy: [Bitboard; Color::NUM_VARIANTS],
+    castle_rights: [CastleRights; Color::NUM_VARIANTS],
+    castling_files: [CastlingFiles; Color::NUM_VARIANTS],
+    en_passant: Option<Square>,
+    side: Color,
+}
+
+impl ChessBoard {
+    /// Return the [PositionKey] of the current position, e.g: to use it in a `HashMap`.
+    pub fn position_key(&self) -> PositionKey {
+        PositionKey {
+            piece_occupancy: self.piece_occupancy,
+            color_occupancy: self.color_occupancy,
+            castle_rights: self.castle_rights,
+            castling_files: self.castling_files,
+            en_passant: self.en_passant,
+            side: self.side,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::board::Move;
+
+    #[test]
+    fn ignores_move_counters() {
+        let position = ChessBoard::default();
+        let mut other = position.clone();
+        other.total_plies += 2;
+        other.half_move_clock += 2;
+        assert_ne!(position, other);
+        assert_eq!(position.position_key(), other.position_key());
+    }
+
+    #[test]
+    fn repetition() {
+        let mut counts = HashMap::new();
+        let mut position = ChessBoard::default();
+        *counts.entry(position.position_key()).or_insert(0) += 1;
+        for chess_move in [
+            Move::new(Square::G1, Square::F3, None),
+            Move::new(Square::G8, Square::F6, None),
+            Move::new(Square::F3, Square::G1, None),
+            Move::new(Square::F6, Square::G8, None),
+        ] {
+            let _ = position.play_move_inplace(chess_move);
+            *counts.entry(position.position_key()).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&ChessBoard::default().position_key()], 2);
+    }
+}
@@ -0,0 +1,74 @@
+use super::ChessBoard;
+#[cfg(any(test, feature = "test-positions"))]
+use crate::fen::FromFen;
+
+impl ChessBoard {
+    /// The starting position of a game, equivalent to [ChessBoard::default].
+    pub fn startpos() -> Self {
+        Self::default()
+    }
+
+    /// The "Kiwipete" position, which exercises most of the move generation edge-cases.
+    #[cfg(any(test, feature = "test-positions"))]
+    pub fn kiwipete() -> Self {
+        Self::named_position("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+    }
+
+    /// A sparse end-game position, with en-passant discovered checks along the rank.
+    #[cfg(any(test, feature = "test-positions"))]
+    pub fn rook_endgame() -> Self {
+        Self::named_position("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1")
+    }
+
+    /// A position with many promotions and captures, where white has lost its castling rights.
+    #[cfg(any(test, feature = "test-positions"))]
+    pub fn promotions() -> Self {
+        Self::named_position("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")
+    }
+
+    /// A middle-game position with an under-promotion giving check.
+    #[cfg(any(test, feature = "test-positions"))]
+    pub fn under_promotion() -> Self {
+        Self::named_position("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8")
+    }
+
+    /// The starting position followed by all named positions, to iterate over in tests.
+    #[cfg(test)]
+    pub(crate) fn test_positions() -> [Self; 5] {
+        [
+            Self::default(),
+            Self::kiwipete(),
+            Self::rook_endgame(),
+            Self::promotions(),
+            Self::under_promotion(),
+        ]
+    }
+
+    #[cfg(any(test, feature = "test-positions"))]
+    fn named_position(fen: &str) -> Self {
+        // Unwrap is fine, named positions are known to be valid
+        Self::from_fen(fen).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn startpos() {
+        assert_eq!(ChessBoard::startpos(), ChessBoard::default());
+    }
+
+    #[test]
+    fn named_positions() {
+        for position in [
+            ChessBoard::kiwipete(),
+            ChessBoard::rook_endgame(),
+            ChessBoard::promotions(),
+            ChessBoard::under_promotion(),
+        ] {
+            assert!(position.is_valid());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::string::{String, ToString};
+use core::fmt::Write as _;
+
+use super::ChessBoard;
+use crate::board::{Color, File, Rank, Square};
+
+/// Display the [ChessBoard] as a grid, from the eighth rank down to the first one, with pieces
+/// shown using their FEN character and `.` for empty squares.
+impl core::fmt::Display for ChessBoard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for rank in (0..Rank::NUM_VARIANTS).rev().map(Rank::from_index) {
+            for file in File::iter() {
+                let square = Square::new(file, rank);
+                let c = match self.piece_on(square).zip(self.color_on(square)) {
+                    Some((piece, color)) => piece.to_fen(color),
+                    None => '.',
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl ChessBoard {
+    /// Return a human-readable rendering of the board, with coordinates, followed by the side to
+    /// move, the castling rights, the en-passant square, and the move clocks.
+    pub fn pretty(&self) -> String {
+        let inner = || -> Result<String, core::fmt::Error> {
+            let mut res = String::new();
+
+            let grid = self.to_string();
+            for (line, rank) in grid.lines().zip((1..=Rank::NUM_VARIANTS).rev()) {
+                writeln!(&mut res, "{} {}", rank, line)?;
+            }
+            writeln!(&mut res, "  abcdefgh")?;
+            writeln!(&mut res)?;
+
+            let side = match self.current_player() {
+                Color::White => "White",
+                Color::Black => "Black",
+            };
+            writeln!(&mut res, "{} to move", side)?;
+            writeln!(&mut res, "Castling: {}", self.castling_fen())?;
+            match self.en_passant() {
+                Some(square) => writeln!(&mut res, "En-passant: {}", square)?,
+                None => writeln!(&mut res, "En-passant: -")?,
+            }
+            writeln!(
+                &mut res,
+                "Half-move clock: {}, full-move number: {}",
+                self.half_move_clock(),
+                self.total_plies() / 2 + 1
+            )?;
+
+            Ok(res)
+        };
+
+        inner().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ChessBoard::default().to_string(),
+            "rnbqkbnr\npppppppp\n........\n........\n........\n........\nPPPPPPPP\nRNBQKBNR\n"
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        let position =
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kq e3 0 1")
+                .unwrap();
+        let pretty = position.pretty();
+        assert!(pretty.starts_with("8 rnbqkbnr\n7 pppppppp\n"));
+        assert!(pretty.contains("4 ....P...\n"));
+        assert!(pretty.contains("1 RNBQKBNR\n  abcdefgh\n"));
+        assert!(pretty.contains("Black to move\n"));
+        assert!(pretty.contains("Castling: Kq\n"));
+        assert!(pretty.contains("En-passant: E3\n"));
+        assert!(pretty.contains("Half-move clock: 0, full-move number: 1\n"));
+    }
+}
@@ -0,0 +1,340 @@
+use alloc::{vec, vec::Vec};
+
+use super::ChessBoard;
+use crate::board::{Bitboard, Color, Move, Piece, Square};
+use crate::eval::PieceValues;
+
+/// Order in which attackers are tried during an exchange, from least to most valuable.
+const SEE_ATTACKER_ORDER: [Piece; Piece::NUM_VARIANTS] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King,
+];
+
+/// Return the default value of a [Piece] for static exchange evaluation.
+#[inline(always)]
+pub(super) fn see_value(piece: Piece) -> i32 {
+    PieceValues::DEFAULT.value(piece)
+}
+
+impl ChessBoard {
+    /// Compute the static exchange evaluation of the given [Move], in centipawns: the material
+    /// balance for the current player at the end of the sequence of captures on its destination
+    /// [Square], where each side recaptures with its least valuable attacker and may choose to
+    /// stop the exchange at any point.
+    pub fn see(&self, chess_move: Move) -> i32 {
+        self.see_with(chess_move, &PieceValues::DEFAULT)
+    }
+
+    /// Compute the static exchange evaluation of the given [Move], see [ChessBoard::see], using
+    /// the given [PieceValues].
+    pub fn see_with(&self, chess_move: Move, values: &PieceValues) -> i32 {
+        let target = chess_move.destination();
+        let mut occupancy = self.see_occupancy(chess_move);
+        let mut side = !self.current_player();
+        // The value of the piece currently standing on the target square
+        let mut on_target = values.value(self.see_attacker(chess_move));
+        let mut gains = vec![self.see_gain(chess_move, values)];
+
+        while let Some((square, piece)) = self.see_next_attacker(target, side, occupancy) {
+            // Unwrap is fine, we always have at least one value in the list
+            gains.push(on_target - gains.last().unwrap());
+            occupancy ^= square;
+            on_target = values.value(piece);
+            side = !side;
+        }
+
+        // Either side can decide to stop capturing if it would lose material.
+        for i in (1..gains.len()).rev() {
+            gains[i - 1] = -core::cmp::max(-gains[i - 1], gains[i]);
+        }
+
+        gains[0]
+    }
+
+    /// Return the list of speculative gains built by the static exchange evaluation of the
+    /// captures on `target`, started by `side`: each entry is the material balance for the side
+    /// making that capture if the exchange stopped right after it, before either side decides to
+    /// stop early. There is one entry per capture, and an empty list if `side` cannot capture.
+    pub fn see_gain_list(&self, target: Square, side: Color) -> Vec<i32> {
+        let values = &PieceValues::DEFAULT;
+        let mut occupancy = self.combined_occupancy();
+        let mut side = side;
+        // The value of the piece currently standing on the target square
+        let mut on_target = self.piece_on(target).map_or(0, |piece| values.value(piece));
+        let mut gains = Vec::new();
+
+        while let Some((square, piece)) = self.see_next_attacker(target, side, occupancy) {
+            gains.push(on_target - gains.last().copied().unwrap_or(0));
+            occupancy ^= square;
+            on_target = values.value(piece);
+            side = !side;
+        }
+
+        gains
+    }
+
+    /// Return true if the static exchange evaluation of the given [Move] is greater or equal to
+    /// `threshold`. This is equivalent to `self.see(chess_move) >= threshold`, but faster as it
+    /// can stop as soon as the result is known.
+    pub fn see_ge(&self, chess_move: Move, threshold: i32) -> bool {
+        self.see_ge_with(chess_move, threshold, &PieceValues::DEFAULT)
+    }
+
+    /// Return true if the static exchange evaluation of the given [Move] is greater or equal to
+    /// `threshold`, see [ChessBoard::see_ge], using the given [PieceValues].
+    pub fn see_ge_with(&self, chess_move: Move, threshold: i32, values: &PieceValues) -> bool {
+        let target = chess_move.destination();
+
+        // Even winning the captured piece for free would not be enough.
+        let mut swap = self.see_gain(chess_move, values) - threshold;
+        if swap < 0 {
+            return false;
+        }
+
+        // Even losing the capturing piece would be enough.
+        swap = values.value(self.see_attacker(chess_move)) - swap;
+        if swap <= 0 {
+            return true;
+        }
+
+        let mut occupancy = self.see_occupancy(chess_move);
+        let mut side = !self.current_player();
+        let mut res = true;
+
+        while let Some((square, piece)) = self.see_next_attacker(target, side, occupancy) {
+            res = !res;
+            swap = values.value(piece) - swap;
+            // The side to move does not need to continue the exchange
+            if swap < res as i32 {
+                break;
+            }
+            occupancy ^= square;
+            side = !side;
+        }
+
+        res
+    }
+
+    /// Return the material gained by playing the given [Move], not accounting for recaptures.
+    pub(super) fn see_gain(&self, chess_move: Move, values: &PieceValues) -> i32 {
+        // A Chess960 king castles onto its own rook, which is not a capture
+        let captured = self
+            .captured_piece(chess_move)
+            .map_or(0, |piece| values.value(piece));
+        let promotion = chess_move
+            .promotion()
+            .map_or(0, |piece| values.value(piece) - values.value(Piece::Pawn));
+        captured + promotion
+    }
+
+    /// Return the [Piece] standing on the destination [Square] after playing the given [Move].
+    fn see_attacker(&self, chess_move: Move) -> Piece {
+        // Unwrap is fine, the move must start from an occupied square
+        chess_move
+            .promotion()
+            .unwrap_or_else(|| self.piece_on(chess_move.start()).unwrap())
+    }
+
+    /// Return the occupancy of the board after playing the given [Move].
+    fn see_occupancy(&self, chess_move: Move) -> Bitboard {
+        let mut occupancy = self.combined_occupancy() ^ chess_move.start();
+        if self.is_see_en_passant(chess_move) {
+            let color = self.current_player();
+            occupancy ^= color
+                .backward_direction()
+                .move_board(chess_move.destination().into_bitboard());
+        }
+        occupancy
+    }
+
+    /// Return true if the given [Move] is an en-passant capture.
+    fn is_see_en_passant(&self, chess_move: Move) -> bool {
+        Some(chess_move.destination()) == self.en_passant()
+            && self.piece_on(chess_move.start()) == Some(Piece::Pawn)
+    }
+
+    /// Return the least valuable piece of the given [Color] that can capture on `target`, given
+    /// the current `occupancy`, or `None` if the exchange cannot go on.
+    fn see_next_attacker(
+        &self,
+        target: Square,
+        side: Color,
+        occupancy: Bitboard,
+    ) -> Option<(Square, Piece)> {
+        let (square, piece) = self.smallest_attacker(target, side, occupancy)?;
+
+        // The king cannot capture a defended piece
+        if piece == Piece::King
+            && !(self.attackers_to(target, occupancy) & self.color_occupancy(!side)).is_empty()
+        {
+            return None;
+        }
+
+        Some((square, piece))
+    }
+
+    /// Return the least valuable piece of the given [Color] attacking `square`, considering only
+    /// the pieces in `occupancy`, or `None` if there are none. Sliding pieces are blocked by
+    /// `occupancy`, so that removing a piece from it reveals x-ray attacks through its square.
+    pub fn smallest_attacker(
+        &self,
+        square: Square,
+        color: Color,
+        occupancy: Bitboard,
+    ) -> Option<(Square, Piece)> {
+        let attackers = self.attackers_to(square, occupancy) & self.color_occupancy(color);
+        SEE_ATTACKER_ORDER.iter().find_map(|&piece| {
+            let board = attackers & self.piece_occupancy(piece);
+            board.any_square().map(|square| (square, piece))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    fn captures(position: &ChessBoard) -> Vec<Move> {
+        let us = position.current_player();
+        let mut res = Vec::new();
+        for target in position.color_occupancy(!us) {
+            let attackers = position.attackers_to(target, position.combined_occupancy())
+                & position.color_occupancy(us);
+            for start in attackers {
+                res.push(Move::new(start, target, None));
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn see_with_piece_values() {
+        let values = PieceValues::DEFAULT.with_value(Piece::Bishop, 350);
+
+        // BxN, PxB
+        let position = ChessBoard::from_fen("4k3/8/2p5/3n4/8/5B2/8/4K3 w - - 0 1").unwrap();
+        let chess_move = Move::new(Square::F3, Square::D5, None);
+        assert_eq!(position.see(chess_move), 0);
+        assert_eq!(position.see_with(chess_move, &values), -50);
+        assert!(position.see_ge(chess_move, 0));
+        assert!(!position.see_ge_with(chess_move, 0, &values));
+
+        // NxB, PxN
+        let position = ChessBoard::from_fen("4k3/8/2p5/3b4/8/4N3/8/4K3 w - - 0 1").unwrap();
+        let chess_move = Move::new(Square::E3, Square::D5, None);
+        assert_eq!(position.see(chess_move), 0);
+        assert_eq!(position.see_with(chess_move, &values), 50);
+        assert!(position.see_ge_with(chess_move, 50, &values));
+    }
+
+    #[test]
+    fn see_undefended() {
+        let position =
+            ChessBoard::from_fen("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1").unwrap();
+        assert_eq!(position.see(Move::new(Square::E1, Square::E5, None)), 100);
+    }
+
+    #[test]
+    fn see_defended() {
+        let position =
+            ChessBoard::from_fen("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1")
+                .unwrap();
+        // NxP, NxN, RxN, BxR, QxB, QxQ: the exchange should stop after black's NxN
+        assert_eq!(position.see(Move::new(Square::D3, Square::E5, None)), -200);
+    }
+
+    #[test]
+    fn see_gain_list() {
+        let position =
+            ChessBoard::from_fen("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1")
+                .unwrap();
+        let (pawn, knight) = (see_value(Piece::Pawn), see_value(Piece::Knight));
+        let (bishop, rook) = (see_value(Piece::Bishop), see_value(Piece::Rook));
+        // NxP, NxN, RxN, BxR, QxB, QxQ
+        let gains = position.see_gain_list(Square::E5, Color::White);
+        assert_eq!(gains.len(), 6);
+        assert_eq!(gains[..3], [pawn, knight - pawn, pawn]);
+        assert_eq!(gains[3], rook - gains[2]);
+        assert_eq!(gains[4], bishop - gains[3]);
+        // Nobody can capture on an unprotected square
+        assert!(position.see_gain_list(Square::A4, Color::White).is_empty());
+    }
+
+    #[test]
+    fn see_king_cannot_capture_defended_piece() {
+        let position = ChessBoard::from_fen("8/8/8/8/8/4k3/4p3/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(position.see(Move::new(Square::E1, Square::E2, None)), -400);
+        let position = ChessBoard::from_fen("8/8/8/8/8/4k3/4p3/3BR1K1 w - - 0 1").unwrap();
+        assert_eq!(position.see(Move::new(Square::E1, Square::E2, None)), 100);
+    }
+
+    #[test]
+    fn see_en_passant() {
+        let position = ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(position.see(Move::new(Square::E5, Square::D6, None)), 100);
+    }
+
+    #[test]
+    fn smallest_attacker() {
+        let position = ChessBoard::from_fen("4k3/8/8/3p4/4P3/8/8/3QK3 w - - 0 1").unwrap();
+        let occupancy = position.combined_occupancy();
+        assert_eq!(
+            position.smallest_attacker(Square::D5, Color::White, occupancy),
+            Some((Square::E4, Piece::Pawn))
+        );
+        assert_eq!(
+            position.smallest_attacker(Square::D5, Color::White, occupancy ^ Square::E4),
+            Some((Square::D1, Piece::Queen))
+        );
+        assert_eq!(
+            position.smallest_attacker(Square::D5, Color::Black, occupancy),
+            None
+        );
+    }
+
+    #[test]
+    fn smallest_attacker_x_ray() {
+        let position = ChessBoard::from_fen("4k3/8/8/3p4/8/8/3R4/3QK3 w - - 0 1").unwrap();
+        let occupancy = position.combined_occupancy();
+        assert_eq!(
+            position.smallest_attacker(Square::D5, Color::White, occupancy),
+            Some((Square::D2, Piece::Rook))
+        );
+        assert_eq!(
+            position.smallest_attacker(Square::D5, Color::White, occupancy ^ Square::D2),
+            Some((Square::D1, Piece::Queen))
+        );
+    }
+
+    #[test]
+    fn see_ge_agrees_with_see() {
+        let position = ChessBoard::from_fen(
+            "r1bq1rk1/pp2bppp/2n1pn2/2pp4/2PP4/2NBPN2/PP1Q1PPP/R1B1K2R w KQ - 0 8",
+        )
+        .unwrap();
+        for position in [position, ChessBoard::kiwipete()] {
+            let captures = captures(&position);
+            assert!(!captures.is_empty());
+            for capture in captures {
+                let see = position.see(capture);
+                for threshold in (-1000..=1000).step_by(50) {
+                    assert_eq!(
+                        position.see_ge(capture, threshold),
+                        see >= threshold,
+                        "{:?} (see = {}) >= {}",
+                        capture,
+                        see,
+                        threshold,
+                    );
+                }
+                assert!(position.see_ge(capture, see));
+                assert!(!position.see_ge(capture, see + 1));
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::ChessBoard;
+
+/// The state of a position where the current player has no legal move, see
+/// [ChessBoard::terminal_state].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Terminal {
+    /// The current player is in check, and lost the game.
+    Checkmate,
+    /// The current player is not in check, the game is a draw.
+    Stalemate,
+}
+
+impl ChessBoard {
+    /// Return the [Terminal] state of the position if the current player has no legal move, or
+    /// `None` if the game can go on. The legal moves are only searched once, making it cheaper
+    /// than calling [ChessBoard::is_checkmate] and [ChessBoard::is_stalemate] in turn.
+    pub fn terminal_state(&self) -> Option<Terminal> {
+        if self.has_legal_move() {
+            return None;
+        }
+        if self.checkers().is_empty() {
+            Some(Terminal::Stalemate)
+        } else {
+            Some(Terminal::Checkmate)
+        }
+    }
+
+    /// Return true if the current player is not in check, and has no legal move.
+    pub fn is_stalemate(&self) -> bool {
+        self.checkers().is_empty() && !self.has_legal_move()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn terminal_state() {
+        let checkmate = ChessBoard::from_fen("4k3/4Q3/4K3/8/8/8/8/8 b - - 0 1").unwrap();
+        assert_eq!(checkmate.terminal_state(), Some(Terminal::Checkmate));
+        assert!(!checkmate.is_stalemate());
+
+        let stalemate = ChessBoard::from_fen("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert_eq!(stalemate.terminal_state(), Some(Terminal::Stalemate));
+        assert!(stalemate.is_stalemate());
+
+        // In check, but not mated
+        let check = ChessBoard::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+        assert_eq!(check.terminal_state(), None);
+        assert_eq!(ChessBoard::default().terminal_state(), None);
+        assert!(!ChessBoard::default().is_stalemate());
+    }
+}
@@ -0,0 +1,285 @@
+use super::ChessBoard;
+use crate::board::{CastleRights, CastlingFiles, Color, File, Move, Piece, Square};
+use crate::movegen;
+use crate::utils::{OnceLock, RandGen, SimpleRng};
+
+/// The random keys used to compute Zobrist hashes.
+struct ZobristKeys {
+    pieces: [[[u64; Square::NUM_VARIANTS]; Piece::NUM_VARIANTS]; Color::NUM_VARIANTS],
+    castle_rights: [[u64; CastleRights::NUM_VARIANTS]; Color::NUM_VARIANTS],
+    en_passant: [u64; File::NUM_VARIANTS],
+    side_to_move: u64,
+    castling_files: [[u64; File::NUM_VARIANTS]; Color::NUM_VARIANTS],
+}
+
+impl ZobristKeys {
+    fn new(rng: &mut dyn RandGen) -> Self {
+        let mut res = Self {
+            pieces: [[[0; Square::NUM_VARIANTS]; Piece::NUM_VARIANTS]; Color::NUM_VARIANTS],
+            castle_rights: [[0; CastleRights::NUM_VARIANTS]; Color::NUM_VARIANTS],
+            en_passant: [0; File::NUM_VARIANTS],
+            side_to_move: 0,
+            castling_files: [[0; File::NUM_VARIANTS]; Color::NUM_VARIANTS],
+        };
+        res.pieces
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|key| *key = rng.gen());
+        // No castle rights should not change the hash
+        for rights in res.castle_rights.iter_mut() {
+            rights[1..].iter_mut().for_each(|key| *key = rng.gen());
+        }
+        res.en_passant.iter_mut().for_each(|key| *key = rng.gen());
+        res.side_to_move = rng.gen();
+        // Generated last, to keep the other keys unchanged
+        res.castling_files
+            .iter_mut()
+            .flatten()
+            .for_each(|key| *key = rng.gen());
+        res
+    }
+}
+
+/// Return the lazily-initialized, deterministic, set of keys.
+fn keys() -> &'static ZobristKeys {
+    static KEYS: OnceLock<ZobristKeys> = OnceLock::new();
+    KEYS.get_or_init(|| ZobristKeys::new(&mut SimpleRng::new()))
+}
+
+/// The key for a [Piece] of the given [Color] standing on a [Square].
+#[inline(always)]
+pub fn moved_piece(color: Color, piece: Piece, square: Square) -> u64 {
+    keys().pieces[color.index()][piece.index()][square.index()]
+}
+
+/// The key for the [CastleRights] of the given [Color].
+#[inline(always)]
+pub fn castling_rights(color: Color, rights: CastleRights) -> u64 {
+    keys().castle_rights[color.index()][rights.index()]
+}
+
+/// The key for a castling rook of the given [Color] standing on the given [File]. It is only used
+/// for rooks outside of their original corners, so that standard positions keep the same hash.
+#[inline(always)]
+pub fn castling_file(color: Color, file: File) -> u64 {
+    keys().castling_files[color.index()][file.index()]
+}
+
+/// Fold the keys for the [CastleRights] of the given [Color], and for its castling rooks which
+/// are outside of their original corners.
+fn castling(color: Color, rights: CastleRights, files: CastlingFiles) -> u64 {
+    let files = files.for_rights(rights);
+    let mut hash = castling_rights(color, rights);
+    if files.king_side() != CastlingFiles::STANDARD.king_side() {
+        hash ^= castling_file(color, files.king_side());
+    }
+    if files.queen_side() != CastlingFiles::STANDARD.queen_side() {
+        hash ^= castling_file(color, files.queen_side());
+    }
+    hash
+}
+
+/// The key for an en-passant target [Square], only its [File] is taken into account.
+#[inline(always)]
+pub fn en_passant(square: Square) -> u64 {
+    keys().en_passant[square.file().index()]
+}
+
+/// The key toggled when it is black's turn to play.
+#[inline(always)]
+pub fn side_to_move() -> u64 {
+    keys().side_to_move
+}
+
+impl ChessBoard {
+    /// Return the Zobrist hash of the current position, it is updated incrementally when playing
+    /// moves.
+    #[inline(always)]
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Compute the Zobrist hash of the position reached after playing the given [Move], without
+    /// modifying the board.
+    pub fn hash_after(&self, chess_move: Move) -> u64 {
+        let us = self.current_player();
+        let them = !us;
+        // Unwrap is fine, the move must start from an occupied square
+        let move_piece = self.piece_on(chess_move.start()).unwrap();
+        let is_en_passant =
+            move_piece == Piece::Pawn && Some(chess_move.destination()) == self.en_passant();
+        let captured_square = if is_en_passant {
+            Square::new(chess_move.destination().file(), chess_move.start().rank())
+        } else {
+            chess_move.destination()
+        };
+        let captured_piece = if !self.color_occupancy(them).contains(captured_square) {
+            None
+        } else {
+            self.piece_on(captured_square)
+        };
+
+        let mut hash = self.hash ^ self.non_reversible_zobrist() ^ side_to_move();
+
+        // Must be computed before the castling rights are updated
+        let castling_squares = self.castling_squares(us, chess_move);
+        let destination = castling_squares.map_or(chess_move.destination(), |(king, _)| king);
+        let dest_piece = chess_move.promotion().unwrap_or(move_piece);
+        hash ^= moved_piece(us, move_piece, chess_move.start());
+        hash ^= moved_piece(us, dest_piece, destination);
+
+        let files = self.castling_files;
+        let mut castle_rights = self.castle_rights;
+        castle_rights[us.index()] = castle_rights[us.index()].after_move(
+            us,
+            files[us.index()],
+            move_piece,
+            chess_move.start(),
+        );
+        if let Some(piece) = captured_piece {
+            hash ^= moved_piece(them, piece, captured_square);
+            castle_rights[them.index()] = castle_rights[them.index()].after_move(
+                them,
+                files[them.index()],
+                piece,
+                captured_square,
+            );
+        }
+        if let Some((_, (rook_start, rook_destination))) = castling_squares {
+            hash ^= moved_piece(us, Piece::Rook, rook_start);
+            hash ^= moved_piece(us, Piece::Rook, rook_destination);
+        }
+        for color in Color::iter() {
+            hash ^= castling(color, castle_rights[color.index()], files[color.index()]);
+        }
+        if let Some(square) = self.en_passant_after(chess_move, move_piece) {
+            // Only hash the target if the opponent can capture on it, see [ChessBoard::en_passant_fen]
+            let origins = movegen::pawn_attacks(us, square);
+            if !origins.is_disjoint(self.occupancy(Piece::Pawn, them)) {
+                hash ^= en_passant(square);
+            }
+        }
+
+        hash
+    }
+
+    /// Iterate over all legal [Move]s for the current player, paired with the Zobrist hash of the
+    /// position they lead to, see [ChessBoard::hash_after].
+    pub fn moves_with_keys(&self) -> impl Iterator<Item = (Move, u64)> + '_ {
+        let moves = self.legal_moves();
+        (0..moves.len()).map(move |index| {
+            let chess_move = moves[index];
+            (chess_move, self.hash_after(chess_move))
+        })
+    }
+
+    /// Compute the Zobrist hash of the current position from scratch, folding the keys of all
+    /// pieces, castling rights, en-passant target, and side to move. It should always be equal to
+    /// [ChessBoard::zobrist_hash], and can be used to verify it.
+    pub fn zobrist_from_scratch(&self) -> u64 {
+        let mut hash = self.non_reversible_zobrist();
+        for color in Color::iter() {
+            for piece in Piece::iter() {
+                for square in self.occupancy(piece, color) {
+                    hash ^= moved_piece(color, piece, square);
+                }
+            }
+        }
+        if self.current_player() == Color::Black {
+            hash ^= side_to_move();
+        }
+        hash
+    }
+
+    /// Compute the part of the Zobrist hash which comes from castling rights, along with their
+    /// rooks, and en-passant. The en-passant target is only taken into account if it can be
+    /// captured, so that positions which only differ by a "phantom" en-passant square hash the
+    /// same, see [ChessBoard::en_passant_fen].
+    pub(super) fn non_reversible_zobrist(&self) -> u64 {
+        let castling = Color::iter()
+            .map(|color| castling(color, self.castle_rights(color), self.castling_files(color)))
+            .fold(0, |lhs, rhs| lhs ^ rhs);
+        castling ^ self.en_passant_fen(true).map_or(0, en_passant)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn incremental_hash() {
+        let mut position = ChessBoard::default();
+        assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+        for chess_move in [
+            Move::new(Square::E2, Square::E4, None),
+            Move::new(Square::D7, Square::D5, None),
+            Move::new(Square::E4, Square::D5, None),
+            Move::new(Square::E8, Square::D7, None),
+        ] {
+            let original = position.clone();
+            let state = position.play_move_inplace(chess_move);
+            assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+            assert_ne!(position.zobrist_hash(), original.zobrist_hash());
+            let mut undone = position.clone();
+            undone.unplay_move(chess_move, state);
+            assert_eq!(undone.zobrist_hash(), original.zobrist_hash());
+        }
+    }
+
+    #[test]
+    fn moves_with_keys() {
+        for position in [ChessBoard::default(), ChessBoard::kiwipete()] {
+            let moves = position.moves_with_keys().collect::<Vec<_>>();
+            assert_eq!(moves.len(), position.count_legal_moves());
+            for (chess_move, key) in moves {
+                assert_eq!(key, position.play_move(chess_move).zobrist_hash());
+            }
+        }
+    }
+
+    #[test]
+    fn from_scratch() {
+        for position in ChessBoard::test_positions() {
+            assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+            for &chess_move in &position.legal_moves() {
+                let position = position.play_move(chess_move);
+                assert_eq!(position.zobrist_hash(), position.zobrist_from_scratch());
+            }
+        }
+    }
+
+    #[test]
+    fn transposition() {
+        let first = ChessBoard::default()
+            .play_move(Move::new(Square::G1, Square::F3, None))
+            .play_move(Move::new(Square::G8, Square::F6, None))
+            .play_move(Move::new(Square::B1, Square::C3, None));
+        let second = ChessBoard::default()
+            .play_move(Move::new(Square::B1, Square::C3, None))
+            .play_move(Move::new(Square::G8, Square::F6, None))
+            .play_move(Move::new(Square::G1, Square::F3, None));
+        assert_eq!(first.zobrist_hash(), second.zobrist_hash());
+    }
+
+    #[test]
+    fn hash_after() {
+        for position in [
+            ChessBoard::default(),
+            ChessBoard::kiwipete(),
+            ChessBoard::promotions(),
+            ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap(),
+        ] {
+            for &chess_move in &position.legal_moves() {
+                assert_eq!(
+                    position.hash_after(chess_move),
+                    position.play_move(chess_move).zobrist_hash(),
+                    "{:?}",
+                    chess_move
+                );
+            }
+        }
+    }
+}
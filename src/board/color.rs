@@ -46,7 +46,7 @@ impl Color {
     /// Should only be called with values that can be output by [Color::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [Color].
@@ -55,6 +55,34 @@ impl Color {
         self as usize
     }
 
+    /// Convert a side to move character, as used in FEN strings, into a [Color]. Returns [None] if
+    /// the character is neither `'w'` nor `'b'`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'w' => Some(Self::White),
+            'b' => Some(Self::Black),
+            _ => None,
+        }
+    }
+
+    /// Convert a [Color] into its side to move character, as used in FEN strings.
+    pub fn to_char(self) -> char {
+        match self {
+            Self::White => 'w',
+            Self::Black => 'b',
+        }
+    }
+
+    /// Return `1` for white, and `-1` for black. Useful to compute scores relative to the side to
+    /// move in negamax-style evaluation.
+    #[inline(always)]
+    pub fn sign(self) -> i32 {
+        match self {
+            Self::White => 1,
+            Self::Black => -1,
+        }
+    }
+
     /// Return the first [Rank] for pieces of the given [Color], where its pieces start.
     #[inline(always)]
     pub fn first_rank(self) -> Rank {
@@ -103,6 +131,20 @@ impl Color {
         }
     }
 
+    /// Return the [Rank] on which pawns of the given [Color] get promoted, i.e: the opponent's
+    /// first rank.
+    #[inline(always)]
+    pub fn promotion_rank(self) -> Rank {
+        (!self).first_rank()
+    }
+
+    /// Return the [Rank] of the en-passant target square when the given [Color] is to move, i.e:
+    /// the opponent's third rank, which its pawns skipped over with a two-square move.
+    #[inline(always)]
+    pub fn en_passant_rank(self) -> Rank {
+        (!self).third_rank()
+    }
+
     /// Which way do pawns advance for this color.
     #[inline(always)]
     pub fn forward_direction(self) -> Direction {
@@ -117,9 +159,15 @@ impl Color {
     pub fn backward_direction(self) -> Direction {
         (!self).forward_direction()
     }
+
+    /// Which way do pawns advance for this color, an alias for [Color::forward_direction].
+    #[inline(always)]
+    pub fn pawn_push_direction(self) -> Direction {
+        self.forward_direction()
+    }
 }
 
-impl std::ops::Not for Color {
+impl core::ops::Not for Color {
     type Output = Color;
 
     fn not(self) -> Self::Output {
@@ -146,6 +194,34 @@ mod test {
         assert_eq!(Color::Black.index(), 1);
     }
 
+    #[test]
+    fn char_round_trip() {
+        for color in Color::iter() {
+            assert_eq!(Color::from_char(color.to_char()), Some(color));
+        }
+        assert_eq!(Color::from_char('w'), Some(Color::White));
+        assert_eq!(Color::from_char('b'), Some(Color::Black));
+        assert_eq!(Color::from_char('x'), None);
+    }
+
+    #[test]
+    fn sign() {
+        assert_eq!(Color::White.sign(), 1);
+        assert_eq!(Color::Black.sign(), -1);
+    }
+
+    #[test]
+    fn promotion_rank() {
+        assert_eq!(Color::White.promotion_rank(), Rank::Eighth);
+        assert_eq!(Color::Black.promotion_rank(), Rank::First);
+    }
+
+    #[test]
+    fn en_passant_rank() {
+        assert_eq!(Color::White.en_passant_rank(), Rank::Sixth);
+        assert_eq!(Color::Black.en_passant_rank(), Rank::Third);
+    }
+
     #[test]
     fn not() {
         assert_eq!(!Color::White, Color::Black);
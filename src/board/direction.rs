@@ -67,6 +67,50 @@ impl Direction {
         Self::KNIGHT_DIRECTIONS.iter().cloned()
     }
 
+    /// Return true if the [Direction] is one that a rook could use.
+    #[inline(always)]
+    pub fn is_orthogonal(self) -> bool {
+        matches!(self, Self::North | Self::West | Self::South | Self::East)
+    }
+
+    /// Return true if the [Direction] is one that a bishop could use.
+    #[inline(always)]
+    pub fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::NorthWest | Self::SouthWest | Self::SouthEast | Self::NorthEast
+        )
+    }
+
+    /// Return true if the [Direction] is one that a knight could use.
+    #[inline(always)]
+    pub fn is_knight(self) -> bool {
+        !(self.is_orthogonal() || self.is_diagonal())
+    }
+
+    /// Return the `(file, rank)` delta corresponding to moving once along the given [Direction],
+    /// e.g: [Direction::NorthNorthEast] is `(1, 2)`.
+    pub fn offset(self) -> (i8, i8) {
+        match self {
+            Self::North => (0, 1),
+            Self::West => (-1, 0),
+            Self::South => (0, -1),
+            Self::East => (1, 0),
+            Self::NorthWest => (-1, 1),
+            Self::SouthWest => (-1, -1),
+            Self::SouthEast => (1, -1),
+            Self::NorthEast => (1, 1),
+            Self::NorthNorthWest => (-1, 2),
+            Self::NorthWestWest => (-2, 1),
+            Self::SouthWestWest => (-2, -1),
+            Self::SouthSouthWest => (-1, -2),
+            Self::SouthSouthEast => (1, -2),
+            Self::SouthEastEast => (2, -1),
+            Self::NorthEastEast => (2, 1),
+            Self::NorthNorthEast => (1, 2),
+        }
+    }
+
     /// Move a [Square] along the given [Direction], unless it would wrap at the end of the board
     pub fn move_square(self, square: Square) -> Option<Square> {
         let res = self.move_board(square.into_bitboard());
@@ -133,7 +177,7 @@ impl Direction {
     /// debug-mode if it happens.
     #[inline(always)]
     pub fn slide_board_with_blockers(self, mut board: Bitboard, blockers: Bitboard) -> Bitboard {
-        debug_assert!(!Self::KNIGHT_DIRECTIONS.contains(&self));
+        debug_assert!(!self.is_knight());
 
         let mut res = Default::default();
 
@@ -154,6 +198,38 @@ mod test {
     use super::*;
     use crate::board::{File, Rank};
 
+    #[test]
+    fn offset() {
+        assert_eq!(Direction::NorthNorthEast.offset(), (1, 2));
+        assert_eq!(Direction::SouthWest.offset(), (-1, -1));
+        for dir in Direction::iter_royalty().chain(Direction::iter_knight()) {
+            let (files, ranks) = dir.offset();
+            for square in Square::iter() {
+                assert_eq!(dir.move_square(square), square.offset(files, ranks));
+            }
+        }
+    }
+
+    #[test]
+    fn classification() {
+        assert!(Direction::North.is_orthogonal());
+        assert!(!Direction::North.is_diagonal());
+        assert!(!Direction::North.is_knight());
+        assert!(Direction::SouthEast.is_diagonal());
+        assert!(!Direction::SouthEast.is_orthogonal());
+        assert!(Direction::NorthNorthEast.is_knight());
+        assert!(!Direction::NorthNorthEast.is_diagonal());
+        for dir in Direction::iter_rook() {
+            assert!(dir.is_orthogonal() && !dir.is_diagonal() && !dir.is_knight());
+        }
+        for dir in Direction::iter_bishop() {
+            assert!(!dir.is_orthogonal() && dir.is_diagonal() && !dir.is_knight());
+        }
+        for dir in Direction::iter_knight() {
+            assert!(!dir.is_orthogonal() && !dir.is_diagonal() && dir.is_knight());
+        }
+    }
+
     #[test]
     fn north() {
         assert_eq!(
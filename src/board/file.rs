@@ -61,7 +61,7 @@ impl File {
     /// Should only be called with values that can be output by [File::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [File].
@@ -71,17 +71,31 @@ impl File {
     }
 
     /// Return the [File] to the left, as seen from white's perspective. Wraps around the board.
+    /// See [File::try_left] for a non-wrapping version.
     pub fn left(self) -> Self {
         // SAFETY: we know the value is in-bounds, through masking
         unsafe { Self::from_index_unchecked(self.index().wrapping_sub(1) & 7) }
     }
 
     /// Return the [File] to the right, as seen from white's perspective. Wraps around the board.
+    /// See [File::try_right] for a non-wrapping version.
     pub fn right(self) -> Self {
         // SAFETY: we know the value is in-bounds, through masking
         unsafe { Self::from_index_unchecked(self.index().wrapping_add(1) & 7) }
     }
 
+    /// Return the [File] to the left, as seen from white's perspective. Returns `None` on the
+    /// edge of the board instead of wrapping around.
+    pub fn try_left(self) -> Option<Self> {
+        Self::try_from_index(self.index().checked_sub(1)?)
+    }
+
+    /// Return the [File] to the right, as seen from white's perspective. Returns `None` on the
+    /// edge of the board instead of wrapping around.
+    pub fn try_right(self) -> Option<Self> {
+        Self::try_from_index(self.index().checked_add(1)?)
+    }
+
     /// Turn a [File] into a [Bitboard] of all squares in that file.
     #[inline(always)]
     pub fn into_bitboard(self) -> Bitboard {
@@ -91,7 +105,7 @@ impl File {
 }
 
 // Ensure that niche-optimization is in effect.
-static_assert!(std::mem::size_of::<Option<File>>() == std::mem::size_of::<File>());
+static_assert!(core::mem::size_of::<Option<File>>() == core::mem::size_of::<File>());
 
 #[cfg(test)]
 mod test {
@@ -125,6 +139,20 @@ mod test {
         assert_eq!(File::H.right(), File::A);
     }
 
+    #[test]
+    fn try_left() {
+        assert_eq!(File::A.try_left(), None);
+        assert_eq!(File::B.try_left(), Some(File::A));
+        assert_eq!(File::H.try_left(), Some(File::G));
+    }
+
+    #[test]
+    fn try_right() {
+        assert_eq!(File::A.try_right(), Some(File::B));
+        assert_eq!(File::G.try_right(), Some(File::H));
+        assert_eq!(File::H.try_right(), None);
+    }
+
     #[test]
     fn into_bitboard() {
         assert_eq!(File::A.into_bitboard(), Bitboard::FILES[0]);
@@ -0,0 +1,66 @@
+use alloc::vec::Vec;
+
+/// The Zobrist hashes of the positions reached during a game, in order, used to detect
+/// repetitions. It does not include the current position.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    hashes: Vec<u64>,
+}
+
+impl History {
+    /// Create an empty [History].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the hash of a position which was left.
+    #[inline(always)]
+    pub fn push(&mut self, hash: u64) {
+        self.hashes.push(hash);
+    }
+
+    /// Forget the most recently recorded hash, returning it, or `None` if the history is empty.
+    #[inline(always)]
+    pub fn pop(&mut self) -> Option<u64> {
+        self.hashes.pop()
+    }
+
+    /// Return the number of recorded positions.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Return true if no position was recorded.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Return the number of times a position with the given hash was recorded.
+    pub fn repetitions(&self, hash: u64) -> usize {
+        self.hashes.iter().filter(|&&h| h == hash).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut history = History::new();
+        assert!(history.is_empty());
+        history.push(42);
+        history.push(1);
+        history.push(42);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.repetitions(42), 2);
+        assert_eq!(history.repetitions(0), 0);
+        assert_eq!(history.pop(), Some(42));
+        assert_eq!(history.repetitions(42), 1);
+        assert_eq!(history.pop(), Some(1));
+        assert_eq!(history.pop(), Some(42));
+        assert_eq!(history.pop(), None);
+    }
+}
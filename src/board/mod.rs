@@ -4,6 +4,9 @@ pub use bitboard::*;
 pub mod castle_rights;
 pub use castle_rights::*;
 
+pub mod castling_files;
+pub use castling_files::*;
+
 pub mod chess_board;
 pub use chess_board::*;
 
@@ -16,12 +19,21 @@ pub use direction::*;
 pub mod file;
 pub use file::*;
 
+pub mod history;
+pub use history::*;
+
 pub mod r#move;
 pub use r#move::*;
 
 pub mod piece;
 pub use piece::*;
 
+pub mod played_game;
+pub use played_game::*;
+
+pub mod position;
+pub use position::*;
+
 pub mod rank;
 pub use rank::*;
 
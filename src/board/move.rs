@@ -1,9 +1,12 @@
-use super::{Piece, Square};
+use super::{File, Piece, Rank, Square};
 
 /// A chess move, containing:
 /// * Starting square.
 /// * Destination square.
 /// * Optional promotion type.
+///
+/// Moves are ordered by their starting [Square], then their destination, then their promotion,
+/// with non-promoting moves first. This gives a canonical order to sort lists of moves by.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Move {
     start: Square,
@@ -39,4 +42,261 @@ impl Move {
     pub fn promotion(self) -> Option<Piece> {
         self.promotion
     }
+
+    /// Return true if this move promotes a pawn.
+    #[inline(always)]
+    pub fn is_promotion(self) -> bool {
+        self.promotion.is_some()
+    }
+
+    /// Get the [Piece] that this move promotes to, see [Move::promotion].
+    #[inline(always)]
+    pub fn promotion_piece(self) -> Option<Piece> {
+        self.promotion
+    }
+
+    /// Expand a pawn move onto the last rank into its four possible promotions, one for each of
+    /// [Piece::PROMOTION_TARGETS], in the same order.
+    #[inline(always)]
+    pub fn promotions(self) -> [Move; 4] {
+        Piece::PROMOTION_TARGETS.map(|piece| Move::new(self.start, self.destination, Some(piece)))
+    }
+
+    /// Pack the move into 16 bits: the start [Square]'s index in the lowest 6 bits, then the
+    /// destination's index in the next 6 bits, and the promotion [Piece] in the highest 4 bits,
+    /// as its index plus one, or zero if the move does not promote. Castling is encoded as any
+    /// other king move.
+    #[inline(always)]
+    pub fn to_u16(self) -> u16 {
+        let promotion = self.promotion.map_or(0, |piece| piece.index() + 1);
+        (self.start.index() | (self.destination.index() << 6) | (promotion << 12)) as u16
+    }
+
+    /// Unpack a move encoded with [Move::to_u16].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the promotion bits do not encode a valid [Piece].
+    #[inline(always)]
+    pub fn from_u16(bits: u16) -> Self {
+        Self::try_from_u16(bits).expect("invalid promotion bits")
+    }
+
+    /// Unpack a move encoded with [Move::to_u16]. Returns [None] if the promotion bits do not
+    /// encode a valid [Piece].
+    pub fn try_from_u16(bits: u16) -> Option<Self> {
+        let bits = bits as usize;
+        let start = Square::from_index(bits & 0x3f);
+        let destination = Square::from_index((bits >> 6) & 0x3f);
+        let promotion = match bits >> 12 {
+            0 => None,
+            index => Some(Piece::try_from_index(index - 1)?),
+        };
+        Some(Self::new(start, destination, promotion))
+    }
+
+    /// Get the [File] of the [Square] that this move starts from.
+    #[inline(always)]
+    pub fn start_file(self) -> File {
+        self.start.file()
+    }
+
+    /// Get the [Rank] of the [Square] that this move starts from.
+    #[inline(always)]
+    pub fn start_rank(self) -> Rank {
+        self.start.rank()
+    }
+
+    /// Get the [File] of the [Square] that this move ends on.
+    #[inline(always)]
+    pub fn destination_file(self) -> File {
+        self.destination.file()
+    }
+
+    /// Get the [Rank] of the [Square] that this move ends on.
+    #[inline(always)]
+    pub fn destination_rank(self) -> Rank {
+        self.destination.rank()
+    }
+}
+
+/// A singular type for all errors that could happen when parsing a [Move] in coordinate notation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoveParseError {
+    /// The input is neither 4 nor 5 characters long.
+    InvalidLength,
+    /// The start or destination square is invalid.
+    InvalidSquare,
+    /// The promotion piece is invalid.
+    InvalidPromotion,
+}
+
+impl core::fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error_msg = match self {
+            Self::InvalidLength => "move is neither 4 nor 5 characters long",
+            Self::InvalidSquare => "invalid start or destination square",
+            Self::InvalidPromotion => "invalid promotion piece",
+        };
+        write!(f, "{}", error_msg)
+    }
+}
+
+impl core::error::Error for MoveParseError {}
+
+/// Parse a [Square] in coordinate notation, e.g: `e4`.
+fn parse_square(s: &[u8]) -> Result<Square, MoveParseError> {
+    match s {
+        [file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Ok(Square::new(
+            File::from_index((file - b'a') as usize),
+            Rank::from_index((rank - b'1') as usize),
+        )),
+        _ => Err(MoveParseError::InvalidSquare),
+    }
+}
+
+/// Parse a [Move] in coordinate notation, e.g: `e2e4` or `e7e8q`, without needing a board.
+impl TryFrom<&str> for Move {
+    type Error = MoveParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (squares, promotion) = match s.as_bytes() {
+            [squares @ .., promotion] if squares.len() == 4 => (squares, Some(promotion)),
+            squares if squares.len() == 4 => (squares, None),
+            _ => return Err(MoveParseError::InvalidLength),
+        };
+        let start = parse_square(&squares[..2])?;
+        let destination = parse_square(&squares[2..])?;
+        let promotion = match promotion {
+            None => None,
+            Some(b'q') => Some(Piece::Queen),
+            Some(b'r') => Some(Piece::Rook),
+            Some(b'b') => Some(Piece::Bishop),
+            Some(b'n') => Some(Piece::Knight),
+            Some(_) => return Err(MoveParseError::InvalidPromotion),
+        };
+        Ok(Self::new(start, destination, promotion))
+    }
+}
+
+/// Parse a [Move] in coordinate notation, see [Move::try_from].
+impl core::str::FromStr for Move {
+    type Err = MoveParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let chess_move = Move::new(Square::B7, Square::A8, Some(Piece::Knight));
+        assert!(chess_move.is_promotion());
+        assert_eq!(chess_move.promotion_piece(), Some(Piece::Knight));
+        assert_eq!(chess_move.start_file(), File::B);
+        assert_eq!(chess_move.start_rank(), Rank::Seventh);
+        assert_eq!(chess_move.destination_file(), File::A);
+        assert_eq!(chess_move.destination_rank(), Rank::Eighth);
+
+        let chess_move = Move::new(Square::E2, Square::E4, None);
+        assert!(!chess_move.is_promotion());
+        assert_eq!(chess_move.promotion_piece(), None);
+    }
+
+    #[test]
+    fn promotions() {
+        assert_eq!(
+            Move::new(Square::B7, Square::A8, None).promotions(),
+            [
+                Move::new(Square::B7, Square::A8, Some(Piece::Queen)),
+                Move::new(Square::B7, Square::A8, Some(Piece::Rook)),
+                Move::new(Square::B7, Square::A8, Some(Piece::Bishop)),
+                Move::new(Square::B7, Square::A8, Some(Piece::Knight)),
+            ]
+        );
+    }
+
+    #[test]
+    fn u16_round_trip() {
+        for chess_move in [
+            Move::new(Square::E2, Square::E4, None),
+            Move::new(Square::A1, Square::H8, None),
+            Move::new(Square::H8, Square::A1, None),
+            Move::new(Square::B7, Square::A8, Some(Piece::Knight)),
+            Move::new(Square::G2, Square::G1, Some(Piece::Queen)),
+            // Castling
+            Move::new(Square::E1, Square::G1, None),
+            Move::new(Square::E8, Square::C8, None),
+        ] {
+            assert_eq!(Move::from_u16(chess_move.to_u16()), chess_move);
+        }
+        assert_eq!(Move::new(Square::A1, Square::A1, None).to_u16(), 0);
+        assert_eq!(
+            Move::new(Square::E7, Square::E8, Some(Piece::Queen)).to_u16(),
+            (2 << 12) | (Square::E8.index() << 6) as u16 | Square::E7.index() as u16
+        );
+        assert_eq!(Move::try_from_u16(0x7000), None);
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(
+            Move::try_from("e2e4"),
+            Ok(Move::new(Square::E2, Square::E4, None))
+        );
+        assert_eq!(
+            Move::try_from("a7b8n"),
+            Ok(Move::new(Square::A7, Square::B8, Some(Piece::Knight)))
+        );
+        assert_eq!(Move::try_from("e2e"), Err(MoveParseError::InvalidLength));
+        assert_eq!(Move::try_from("e2e4q1"), Err(MoveParseError::InvalidLength));
+        assert_eq!(Move::try_from("e2e9"), Err(MoveParseError::InvalidSquare));
+        assert_eq!(Move::try_from("i2e4"), Err(MoveParseError::InvalidSquare));
+        assert_eq!(
+            Move::try_from("e7e8k"),
+            Err(MoveParseError::InvalidPromotion)
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "h7h8q".parse::<Move>(),
+            Ok(Move::new(Square::H7, Square::H8, Some(Piece::Queen)))
+        );
+        assert_eq!("".parse::<Move>(), Err(MoveParseError::InvalidLength));
+    }
+
+    #[test]
+    fn ord() {
+        assert!(Move::new(Square::A1, Square::H8, None) < Move::new(Square::A2, Square::A3, None));
+        assert!(Move::new(Square::E2, Square::E3, None) < Move::new(Square::E2, Square::E4, None));
+        assert!(
+            Move::new(Square::B7, Square::B8, None)
+                < Move::new(Square::B7, Square::B8, Some(Piece::Queen))
+        );
+    }
+
+    #[test]
+    fn canonical_sort() {
+        use crate::board::ChessBoard;
+        use crate::fen::FromFen;
+
+        let position = ChessBoard::from_fen("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        let mut first = position.legal_moves().iter().copied().collect::<Vec<_>>();
+        let mut second = position
+            .legal_moves()
+            .iter()
+            .rev()
+            .copied()
+            .collect::<Vec<_>>();
+        first.sort();
+        second.sort();
+        assert_eq!(first, second);
+        assert!(first.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 }
@@ -1,3 +1,5 @@
+use super::Color;
+
 /// An enum representing the type of a piece.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Piece {
@@ -22,6 +24,9 @@ impl Piece {
         Self::Pawn,
     ];
 
+    /// The pieces that a pawn can be promoted to, in decreasing order of value.
+    pub const PROMOTION_TARGETS: [Self; 4] = [Self::Queen, Self::Rook, Self::Bishop, Self::Knight];
+
     /// Iterate over all piece types.
     pub fn iter() -> impl Iterator<Item = Self> {
         Self::ALL.iter().cloned()
@@ -55,7 +60,7 @@ impl Piece {
     /// Should only be called with values that can be output by [Piece::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [Piece].
@@ -63,6 +68,23 @@ impl Piece {
     pub fn index(self) -> usize {
         self as usize
     }
+
+    /// Return the character representing the [Piece] of the given [Color] in a FEN string, e.g:
+    /// `'N'` for a white knight or `'q'` for a black queen.
+    pub fn to_fen(self, color: Color) -> char {
+        let c = match self {
+            Self::King => 'k',
+            Self::Queen => 'q',
+            Self::Rook => 'r',
+            Self::Bishop => 'b',
+            Self::Knight => 'n',
+            Self::Pawn => 'p',
+        };
+        match color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -82,4 +104,11 @@ mod test {
         assert_eq!(Piece::Queen.index(), 1);
         assert_eq!(Piece::Pawn.index(), 5);
     }
+
+    #[test]
+    fn to_fen() {
+        assert_eq!(Piece::Knight.to_fen(Color::White), 'N');
+        assert_eq!(Piece::Queen.to_fen(Color::Black), 'q');
+        assert_eq!(Piece::Pawn.to_fen(Color::White), 'P');
+    }
 }
@@ -0,0 +1,118 @@
+use alloc::vec::Vec;
+
+use super::{ChessBoard, History, Move, NonReversibleState};
+
+/// A [ChessBoard] along with the [Move]s played to reach it, allowing them to be undone, and the
+/// [History] of positions for repetition detection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlayedGame {
+    board: ChessBoard,
+    moves: Vec<(Move, NonReversibleState)>,
+    history: History,
+}
+
+impl PlayedGame {
+    /// Start a new game from the given [ChessBoard].
+    pub fn new(board: ChessBoard) -> Self {
+        Self {
+            board,
+            moves: Vec::new(),
+            history: History::new(),
+        }
+    }
+
+    /// Return the current [ChessBoard].
+    #[inline(always)]
+    pub fn board(&self) -> &ChessBoard {
+        &self.board
+    }
+
+    /// Return the [History] of positions which were left to reach the current one.
+    #[inline(always)]
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Iterate over the [Move]s played since the start of the game, in order.
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.moves.iter().map(|(chess_move, _)| *chess_move)
+    }
+
+    /// Play the given [Move].
+    pub fn push(&mut self, chess_move: Move) {
+        self.history.push(self.board.zobrist_hash());
+        let state = self.board.play_move_inplace(chess_move);
+        self.moves.push((chess_move, state));
+    }
+
+    /// Undo the last [Move] played, returning it, or `None` if no move was played.
+    pub fn pop(&mut self) -> Option<Move> {
+        let (chess_move, state) = self.moves.pop()?;
+        self.board.unplay_move(chess_move, state);
+        self.history.pop();
+        Some(chess_move)
+    }
+
+    /// Return the number of times the current position was reached, including the current one.
+    pub fn repetitions(&self) -> usize {
+        self.history.repetitions(self.board.zobrist_hash()) + 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+
+    #[test]
+    fn push_pop() {
+        let mut game = PlayedGame::default();
+        let moves = [
+            Move::new(Square::E2, Square::E4, None),
+            Move::new(Square::E7, Square::E5, None),
+            Move::new(Square::G1, Square::F3, None),
+        ];
+        for chess_move in moves {
+            game.push(chess_move);
+        }
+        assert_eq!(game.history().len(), 3);
+        assert_eq!(game.moves().collect::<Vec<_>>(), moves);
+        for chess_move in moves.into_iter().rev() {
+            assert_eq!(game.pop(), Some(chess_move));
+        }
+        assert_eq!(game.pop(), None);
+        assert_eq!(game.board(), &ChessBoard::default());
+        assert!(game.history().is_empty());
+    }
+
+    #[test]
+    fn repetitions() {
+        let mut game = PlayedGame::default();
+        assert_eq!(game.repetitions(), 1);
+        for _ in 0..2 {
+            game.push(Move::new(Square::G1, Square::F3, None));
+            game.push(Move::new(Square::G8, Square::F6, None));
+            game.push(Move::new(Square::F3, Square::G1, None));
+            game.push(Move::new(Square::F6, Square::G8, None));
+        }
+        assert_eq!(game.repetitions(), 3);
+        game.pop();
+        assert_eq!(game.repetitions(), 2);
+    }
+
+    #[test]
+    fn repetitions_after_double_push() {
+        let mut game = PlayedGame::default();
+        // The en-passant square after the double push cannot be captured on
+        game.push(Move::new(Square::E2, Square::E4, None));
+        assert_eq!(game.board().en_passant(), Some(Square::E3));
+        for _ in 0..2 {
+            game.push(Move::new(Square::G8, Square::F6, None));
+            game.push(Move::new(Square::G1, Square::F3, None));
+            game.push(Move::new(Square::F6, Square::G8, None));
+            game.push(Move::new(Square::F3, Square::G1, None));
+        }
+        assert_eq!(game.board().en_passant(), None);
+        assert_eq!(game.repetitions(), 3);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{ChessBoard, Color, History, IllegalMove, MaterialDraw, Move, PlayedGame, Terminal};
+use crate::movegen::MoveList;
+
+/// The way a game has ended, see [Position::outcome].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    /// The given [Color] won by checkmating its opponent.
+    Checkmate(Color),
+    /// The current player has no legal move, but is not in check.
+    Stalemate,
+    /// The current position was reached for the third time.
+    ThreefoldRepetition,
+    /// No pawn was moved, and no piece was captured, in the last fifty moves.
+    FiftyMoveRule,
+    /// Neither side has enough material left to checkmate, see [MaterialDraw::Dead].
+    InsufficientMaterial,
+}
+
+/// A game of chess, combining a [ChessBoard], its Zobrist hash, and the [History] of positions
+/// reached so far. Only legal [Move]s can be played, and they can be undone.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    game: PlayedGame,
+}
+
+impl Position {
+    /// Start a new game from the given [ChessBoard].
+    pub fn new(board: ChessBoard) -> Self {
+        Self {
+            game: PlayedGame::new(board),
+        }
+    }
+
+    /// Return the current [ChessBoard].
+    #[inline(always)]
+    pub fn board(&self) -> &ChessBoard {
+        self.game.board()
+    }
+
+    /// Return the [History] of positions which were left to reach the current one.
+    #[inline(always)]
+    pub fn history(&self) -> &History {
+        self.game.history()
+    }
+
+    /// Return the Zobrist hash of the current position.
+    #[inline(always)]
+    pub fn hash(&self) -> u64 {
+        self.board().zobrist_hash()
+    }
+
+    /// Return the list of all legal [Move]s in the current position.
+    pub fn legal_moves(&self) -> MoveList {
+        self.board().legal_moves()
+    }
+
+    /// Play the given [Move] if it is legal. Otherwise, return Err([IllegalMove]) explaining why
+    /// it was rejected, and leave the position untouched.
+    pub fn make(&mut self, chess_move: Move) -> Result<(), IllegalMove> {
+        self.board().check_legality(chess_move)?;
+        self.game.push(chess_move);
+        Ok(())
+    }
+
+    /// Undo the last [Move] played, returning it, or `None` if no move was played.
+    pub fn unmake(&mut self) -> Option<Move> {
+        self.game.pop()
+    }
+
+    /// Return the [Outcome] of the game if it is over, or `None` if it can go on. Checkmate takes
+    /// precedence over the fifty-move rule.
+    pub fn outcome(&self) -> Option<Outcome> {
+        let board = self.board();
+        match board.terminal_state() {
+            Some(Terminal::Checkmate) => return Some(Outcome::Checkmate(!board.current_player())),
+            Some(Terminal::Stalemate) => return Some(Outcome::Stalemate),
+            None => {}
+        }
+        if self.game.repetitions() >= 3 {
+            Some(Outcome::ThreefoldRepetition)
+        } else if board.half_move_clock() >= 100 {
+            Some(Outcome::FiftyMoveRule)
+        } else if board.material_draw() == Some(MaterialDraw::Dead) {
+            Some(Outcome::InsufficientMaterial)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<ChessBoard> for Position {
+    fn from(board: ChessBoard) -> Self {
+        Self::new(board)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn make_unmake_repetition() {
+        let mut position = Position::default();
+        let start_hash = position.hash();
+        assert_eq!(position.outcome(), None);
+
+        let moves = [
+            Move::new(Square::G1, Square::F3, None),
+            Move::new(Square::G8, Square::F6, None),
+            Move::new(Square::F3, Square::G1, None),
+            Move::new(Square::F6, Square::G8, None),
+        ];
+        for chess_move in moves.into_iter().chain(moves) {
+            assert_eq!(position.outcome(), None);
+            position.make(chess_move).unwrap();
+        }
+        assert_eq!(position.hash(), start_hash);
+        assert_eq!(position.outcome(), Some(Outcome::ThreefoldRepetition));
+
+        // Illegal moves are rejected
+        assert_eq!(
+            position.make(Move::new(Square::E2, Square::E5, None)),
+            Err(IllegalMove::UnreachableSquare)
+        );
+        assert_eq!(position.history().len(), 8);
+
+        while position.unmake().is_some() {}
+        assert_eq!(position.board(), &ChessBoard::default());
+        assert_eq!(position.hash(), start_hash);
+        assert!(position.history().is_empty());
+    }
+
+    #[test]
+    fn outcome() {
+        let outcome = |fen| Position::from(ChessBoard::from_fen(fen).unwrap()).outcome();
+        assert_eq!(
+            outcome("4k3/4Q3/4K3/8/8/8/8/8 b - - 0 1"),
+            Some(Outcome::Checkmate(Color::White))
+        );
+        assert_eq!(
+            outcome("k7/8/1Q6/8/8/8/8/4K3 b - - 0 1"),
+            Some(Outcome::Stalemate)
+        );
+        assert_eq!(
+            outcome("4k3/8/8/8/8/8/8/R3K3 w - - 100 80"),
+            Some(Outcome::FiftyMoveRule)
+        );
+        assert_eq!(
+            outcome("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+            Some(Outcome::InsufficientMaterial)
+        );
+        assert_eq!(outcome("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"), None);
+    }
+}
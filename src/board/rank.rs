@@ -61,7 +61,7 @@ impl Rank {
     /// Should only be called with values that can be output by [Rank::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [Rank].
@@ -71,17 +71,31 @@ impl Rank {
     }
 
     /// Return the [Rank] one-row up, as seen from white's perspective. Wraps around the board.
+    /// See [Rank::try_up] for a non-wrapping version.
     pub fn up(self) -> Self {
         // SAFETY: we know the value is in-bounds, through masking
         unsafe { Self::from_index_unchecked(self.index().wrapping_add(1) & 7) }
     }
 
     /// Return the [Rank] one-row down, as seen from white's perspective. Wraps around the board.
+    /// See [Rank::try_down] for a non-wrapping version.
     pub fn down(self) -> Self {
         // SAFETY: we know the value is in-bounds, through masking
         unsafe { Self::from_index_unchecked(self.index().wrapping_sub(1) & 7) }
     }
 
+    /// Return the [Rank] one-row up, as seen from white's perspective. Returns `None` on the
+    /// edge of the board instead of wrapping around.
+    pub fn try_up(self) -> Option<Self> {
+        Self::try_from_index(self.index().checked_add(1)?)
+    }
+
+    /// Return the [Rank] one-row down, as seen from white's perspective. Returns `None` on the
+    /// edge of the board instead of wrapping around.
+    pub fn try_down(self) -> Option<Self> {
+        Self::try_from_index(self.index().checked_sub(1)?)
+    }
+
     /// Turn a [Rank] into a [Bitboard] of all squares in that rank.
     #[inline(always)]
     pub fn into_bitboard(self) -> Bitboard {
@@ -91,7 +105,7 @@ impl Rank {
 }
 
 // Ensure that niche-optimization is in effect.
-static_assert!(std::mem::size_of::<Option<Rank>>() == std::mem::size_of::<Rank>());
+static_assert!(core::mem::size_of::<Option<Rank>>() == core::mem::size_of::<Rank>());
 
 #[cfg(test)]
 mod test {
@@ -125,6 +139,20 @@ mod test {
         assert_eq!(Rank::Eighth.down(), Rank::Seventh);
     }
 
+    #[test]
+    fn try_up() {
+        assert_eq!(Rank::First.try_up(), Some(Rank::Second));
+        assert_eq!(Rank::Seventh.try_up(), Some(Rank::Eighth));
+        assert_eq!(Rank::Eighth.try_up(), None);
+    }
+
+    #[test]
+    fn try_down() {
+        assert_eq!(Rank::First.try_down(), None);
+        assert_eq!(Rank::Second.try_down(), Some(Rank::First));
+        assert_eq!(Rank::Eighth.try_down(), Some(Rank::Seventh));
+    }
+
     #[test]
     fn into_bitboard() {
         assert_eq!(Rank::First.into_bitboard(), Bitboard::RANKS[0]);
@@ -1,5 +1,6 @@
-use super::{Bitboard, File, Rank};
-use crate::utils::static_assert;
+use super::{Bitboard, Color, File, Rank};
+use crate::movegen;
+use crate::utils::{static_assert, OnceLock};
 
 /// Represent a square on a chessboard. Defined in the same order as the
 /// [Bitboard] squares.
@@ -16,8 +17,8 @@ pub enum Square {
     H1, H2, H3, H4, H5, H6, H7, H8,
 }
 
-impl std::fmt::Display for Square {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Square {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
@@ -78,7 +79,7 @@ impl Square {
     /// Should only be called with values that can be output by [Square::index()].
     #[inline(always)]
     pub unsafe fn from_index_unchecked(index: usize) -> Self {
-        std::mem::transmute(index as u8)
+        core::mem::transmute(index as u8)
     }
 
     /// Return the index of a given [Square].
@@ -118,10 +119,91 @@ impl Square {
     pub fn into_bitboard(self) -> Bitboard {
         Bitboard(1 << (self as usize))
     }
+
+    /// Return the [Square] as seen from the given [Color]'s side of the board: unchanged for white,
+    /// with its rank flipped for black, e.g: [Square::A2] becomes [Square::A7].
+    #[inline(always)]
+    pub fn relative_to(self, color: Color) -> Self {
+        match color {
+            Color::White => self,
+            // SAFETY: we know the value is in-bounds
+            Color::Black => unsafe { Self::from_index_unchecked(self.index() ^ 7) },
+        }
+    }
+
+    /// Return the [Square] directly in front of this one, as seen from the given [Color]'s side of
+    /// the board, or `None` if it would leave the board.
+    #[inline(always)]
+    pub fn forward(self, color: Color) -> Option<Self> {
+        color.forward_direction().move_square(self)
+    }
+
+    /// Return the [Square] directly behind this one, as seen from the given [Color]'s side of the
+    /// board, or `None` if it would leave the board.
+    #[inline(always)]
+    pub fn backward(self, color: Color) -> Option<Self> {
+        color.backward_direction().move_square(self)
+    }
+
+    /// Move the [Square] by the given number of files and ranks, returning `None` if it would
+    /// leave the board.
+    pub fn offset(self, files: i8, ranks: i8) -> Option<Self> {
+        let file = self.file_index().checked_add_signed(files as isize)?;
+        let rank = self.rank_index().checked_add_signed(ranks as isize)?;
+        Some(Self::new(
+            File::try_from_index(file)?,
+            Rank::try_from_index(rank)?,
+        ))
+    }
+
+    /// Return the Manhattan distance to the nearest center [Square] (D4, D5, E4, or E5), ranging
+    /// from 0 in the center to 6 in the corners.
+    #[inline(always)]
+    pub fn center_manhattan_distance(self) -> u8 {
+        // Distance to the center along a single axis, with indices in the `0..8` range
+        let center_distance = |index: usize| if index < 4 { 3 - index } else { index - 4 };
+        (center_distance(self.file_index()) + center_distance(self.rank_index())) as u8
+    }
+
+    /// Return the number of squares between this [Square] and the nearest edge of the board,
+    /// ranging from 0 on the edge to 3 in the center.
+    #[inline(always)]
+    pub fn distance_to_edge(self) -> u8 {
+        let edge_distance = |index: usize| index.min(7 - index);
+        edge_distance(self.file_index()).min(edge_distance(self.rank_index())) as u8
+    }
+
+    /// Return the minimum number of moves a knight needs to go from this [Square] to `other`.
+    pub fn knight_distance(self, other: Square) -> u8 {
+        static KNIGHT_DISTANCES: OnceLock<[[u8; Square::NUM_VARIANTS]; Square::NUM_VARIANTS]> =
+            OnceLock::new();
+        KNIGHT_DISTANCES.get_or_init(|| {
+            let mut res = [[0; Square::NUM_VARIANTS]; Square::NUM_VARIANTS];
+            for square in Square::iter() {
+                // Breadth-first search, one knight move at a time
+                let mut reached = square.into_bitboard();
+                let mut frontier = reached;
+                let mut distance = 0;
+                while !frontier.is_empty() {
+                    distance += 1;
+                    frontier = frontier
+                        .into_iter()
+                        .map(movegen::knight_moves)
+                        .fold(Bitboard::EMPTY, |lhs, rhs| lhs | rhs)
+                        - reached;
+                    for target in frontier {
+                        res[square.index()][target.index()] = distance;
+                    }
+                    reached |= frontier;
+                }
+            }
+            res
+        })[self.index()][other.index()]
+    }
 }
 
 /// Shift the square's index left by the amount given.
-impl std::ops::Shl<usize> for Square {
+impl core::ops::Shl<usize> for Square {
     type Output = Square;
 
     #[inline(always)]
@@ -132,7 +214,7 @@ impl std::ops::Shl<usize> for Square {
 }
 
 /// Shift the square's index right by the amount given.
-impl std::ops::Shr<usize> for Square {
+impl core::ops::Shr<usize> for Square {
     type Output = Square;
 
     #[inline(always)]
@@ -143,7 +225,7 @@ impl std::ops::Shr<usize> for Square {
 }
 
 /// Return a board containing all squares but the one given.
-impl std::ops::Not for Square {
+impl core::ops::Not for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -153,7 +235,7 @@ impl std::ops::Not for Square {
 }
 
 /// Treat the square as a singleton board, and apply the operator.
-impl std::ops::BitOr<Square> for Square {
+impl core::ops::BitOr<Square> for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -163,7 +245,7 @@ impl std::ops::BitOr<Square> for Square {
 }
 
 /// Treat the square as a singleton board, and apply the operator.
-impl std::ops::BitOr<Bitboard> for Square {
+impl core::ops::BitOr<Bitboard> for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -173,7 +255,17 @@ impl std::ops::BitOr<Bitboard> for Square {
 }
 
 /// Treat the square as a singleton board, and apply the operator.
-impl std::ops::BitAnd<Bitboard> for Square {
+impl core::ops::BitAnd<Square> for Square {
+    type Output = Bitboard;
+
+    #[inline(always)]
+    fn bitand(self, rhs: Square) -> Self::Output {
+        self.into_bitboard() & rhs.into_bitboard()
+    }
+}
+
+/// Treat the square as a singleton board, and apply the operator.
+impl core::ops::BitAnd<Bitboard> for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -183,7 +275,17 @@ impl std::ops::BitAnd<Bitboard> for Square {
 }
 
 /// Treat the square as a singleton board, and apply the operator.
-impl std::ops::BitXor<Bitboard> for Square {
+impl core::ops::BitXor<Square> for Square {
+    type Output = Bitboard;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Square) -> Self::Output {
+        self.into_bitboard() ^ rhs.into_bitboard()
+    }
+}
+
+/// Treat the square as a singleton board, and apply the operator.
+impl core::ops::BitXor<Bitboard> for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -193,7 +295,17 @@ impl std::ops::BitXor<Bitboard> for Square {
 }
 
 /// Treat the square as a singleton board, and apply the operator.
-impl std::ops::Sub<Bitboard> for Square {
+impl core::ops::Sub<Square> for Square {
+    type Output = Bitboard;
+
+    #[inline(always)]
+    fn sub(self, rhs: Square) -> Self::Output {
+        self.into_bitboard() - rhs.into_bitboard()
+    }
+}
+
+/// Treat the square as a singleton board, and apply the operator.
+impl core::ops::Sub<Bitboard> for Square {
     type Output = Bitboard;
 
     #[inline(always)]
@@ -203,7 +315,7 @@ impl std::ops::Sub<Bitboard> for Square {
 }
 
 // Ensure that niche-optimization is in effect.
-static_assert!(std::mem::size_of::<Option<Square>>() == std::mem::size_of::<Square>());
+static_assert!(core::mem::size_of::<Option<Square>>() == core::mem::size_of::<Square>());
 
 #[cfg(test)]
 mod test {
@@ -244,6 +356,49 @@ mod test {
         assert_eq!(Square::H8.rank(), Rank::Eighth);
     }
 
+    #[test]
+    fn knight_distance() {
+        assert_eq!(Square::A1.knight_distance(Square::A1), 0);
+        assert_eq!(Square::A1.knight_distance(Square::B3), 1);
+        assert_eq!(Square::A1.knight_distance(Square::B2), 4);
+        assert_eq!(Square::A1.knight_distance(Square::H8), 6);
+        assert_eq!(Square::E4.knight_distance(Square::E5), 3);
+        for square in Square::iter() {
+            for other in Square::iter() {
+                assert_eq!(square.knight_distance(other), other.knight_distance(square));
+                assert!(square.knight_distance(other) <= 6);
+            }
+        }
+    }
+
+    #[test]
+    fn center_manhattan_distance() {
+        for square in [Square::A1, Square::A8, Square::H1, Square::H8] {
+            assert_eq!(square.center_manhattan_distance(), 6);
+        }
+        for square in [Square::D4, Square::D5, Square::E4, Square::E5] {
+            assert_eq!(square.center_manhattan_distance(), 0);
+        }
+        assert_eq!(Square::C3.center_manhattan_distance(), 2);
+        assert_eq!(Square::E7.center_manhattan_distance(), 2);
+        assert_eq!(Square::H4.center_manhattan_distance(), 3);
+        for square in Square::iter() {
+            assert!(square.center_manhattan_distance() <= 6);
+        }
+    }
+
+    #[test]
+    fn distance_to_edge() {
+        for square in [Square::A1, Square::A5, Square::C8, Square::H8] {
+            assert_eq!(square.distance_to_edge(), 0);
+        }
+        for square in [Square::D4, Square::D5, Square::E4, Square::E5] {
+            assert_eq!(square.distance_to_edge(), 3);
+        }
+        assert_eq!(Square::B7.distance_to_edge(), 1);
+        assert_eq!(Square::C4.distance_to_edge(), 2);
+    }
+
     #[test]
     fn left_shift() {
         assert_eq!(Square::A1 << 1, Square::A2);
@@ -271,6 +426,54 @@ mod test {
         assert_eq!(Square::A1 & Bitboard::FILES[0], Square::A1.into_bitboard());
     }
 
+    #[test]
+    fn relative_to() {
+        assert_eq!(Square::A2.relative_to(Color::White), Square::A2);
+        assert_eq!(Square::A2.relative_to(Color::Black), Square::A7);
+        assert_eq!(Square::E1.relative_to(Color::Black), Square::E8);
+        assert_eq!(Square::H5.relative_to(Color::Black), Square::H4);
+        for square in Square::iter() {
+            assert_eq!(square.relative_to(Color::Black).file(), square.file());
+            assert_eq!(
+                square.relative_to(Color::Black).relative_to(Color::Black),
+                square
+            );
+        }
+    }
+
+    #[test]
+    fn forward_backward() {
+        assert_eq!(Square::E2.forward(Color::White), Some(Square::E3));
+        assert_eq!(Square::E7.forward(Color::Black), Some(Square::E6));
+        assert_eq!(Square::E8.forward(Color::White), None);
+        assert_eq!(Square::E1.forward(Color::Black), None);
+        assert_eq!(Square::E3.backward(Color::White), Some(Square::E2));
+        assert_eq!(Square::E6.backward(Color::Black), Some(Square::E7));
+        assert_eq!(Square::A1.backward(Color::White), None);
+        assert_eq!(Square::H8.backward(Color::Black), None);
+    }
+
+    #[test]
+    fn offset() {
+        assert_eq!(Square::E4.offset(1, 1), Some(Square::F5));
+        assert_eq!(Square::E4.offset(-4, -3), Some(Square::A1));
+        assert_eq!(Square::E4.offset(0, 0), Some(Square::E4));
+        assert_eq!(Square::H1.offset(1, 0), None);
+        assert_eq!(Square::A1.offset(0, -1), None);
+        assert_eq!(Square::A8.offset(0, 1), None);
+    }
+
+    #[test]
+    fn square_ops() {
+        assert_eq!(Square::A1 & Square::A1, Square::A1.into_bitboard());
+        assert_eq!(Square::A1 & Square::A2, Bitboard::EMPTY);
+        assert_eq!(Square::A1 | Square::A1, Square::A1.into_bitboard());
+        assert_eq!(Square::A1 ^ Square::A1, Bitboard::EMPTY);
+        assert_eq!(Square::A1 ^ Square::A2, Square::A1 | Square::A2);
+        assert_eq!(Square::A1 - Square::A1, Bitboard::EMPTY);
+        assert_eq!(Square::A1 - Square::A2, Square::A1.into_bitboard());
+    }
+
     #[test]
     fn xor() {
         assert_eq!(Square::A1 ^ Bitboard::FILES[0], Bitboard(0xff - 1));
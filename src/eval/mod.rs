@@ -0,0 +1,7 @@
+// Piece values
+mod piece_values;
+pub use piece_values::*;
+
+// Evaluation scores
+mod score;
+pub use score::*;
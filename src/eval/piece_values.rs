@@ -0,0 +1,53 @@
+use crate::board::Piece;
+
+/// The value of each [Piece], in centipawns, used when evaluating material, e.g: during static
+/// exchange evaluation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PieceValues([i32; Piece::NUM_VARIANTS]);
+
+impl PieceValues {
+    /// The default values: 900 for a queen, 500 for a rook, 300 for minor pieces, 100 for a
+    /// pawn. The king is given a large value, so that losing it is never worth it.
+    pub const DEFAULT: Self = Self([20_000, 900, 500, 300, 300, 100]);
+
+    /// Create a set of [PieceValues], indexed by [Piece::index].
+    #[inline(always)]
+    pub const fn new(values: [i32; Piece::NUM_VARIANTS]) -> Self {
+        Self(values)
+    }
+
+    /// Return the value of the given [Piece].
+    #[inline(always)]
+    pub fn value(&self, piece: Piece) -> i32 {
+        self.0[piece.index()]
+    }
+
+    /// Change the value of the given [Piece], and return the resulting [PieceValues].
+    #[inline(always)]
+    pub fn with_value(mut self, piece: Piece, value: i32) -> Self {
+        self.0[piece.index()] = value;
+        self
+    }
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn value() {
+        let values = PieceValues::default();
+        assert_eq!(values, PieceValues::DEFAULT);
+        assert_eq!(values.value(Piece::Queen), 900);
+        assert_eq!(values.value(Piece::Pawn), 100);
+        let values = values.with_value(Piece::Bishop, 330);
+        assert_eq!(values.value(Piece::Bishop), 330);
+        assert_eq!(values.value(Piece::Knight), 300);
+    }
+}
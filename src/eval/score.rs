@@ -0,0 +1,184 @@
+/// The score of a position, from the point of view of the side to move. It is either a normal
+/// evaluation in centipawns, or a mate score close to [Score::MATE], with mates found in fewer
+/// plies being closer to it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Score(i32);
+
+impl Score {
+    /// A draw, or an equal position.
+    pub const ZERO: Self = Self(0);
+
+    /// The score of a checkmate delivered on the current ply, the highest score possible.
+    pub const MATE: Self = Self(32000);
+
+    /// The maximum number of plies for which mate scores can be represented.
+    pub const MAX_MATE_PLIES: i32 = 1000;
+
+    /// The highest absolute value of a normal evaluation, scores above it are mate scores.
+    const MAX_EVAL: i32 = Self::MATE.0 - Self::MAX_MATE_PLIES;
+
+    /// Create a normal evaluation from a value in centipawns, clamped out of the mate range.
+    #[inline(always)]
+    pub fn centipawns(value: i32) -> Self {
+        Self(value.clamp(-Self::MAX_EVAL, Self::MAX_EVAL))
+    }
+
+    /// Create the score of delivering a checkmate in the given number of plies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `plies` is not in the `0..MAX_MATE_PLIES` range.
+    #[inline(always)]
+    pub fn mate_in(plies: i32) -> Self {
+        assert!((0..Self::MAX_MATE_PLIES).contains(&plies));
+        Self(Self::MATE.0 - plies)
+    }
+
+    /// Create the score of being checkmated in the given number of plies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `plies` is not in the `0..MAX_MATE_PLIES` range.
+    #[inline(always)]
+    pub fn mated_in(plies: i32) -> Self {
+        -Self::mate_in(plies)
+    }
+
+    /// Return the raw value of the score, in centipawns for normal evaluations.
+    #[inline(always)]
+    pub fn value(self) -> i32 {
+        self.0
+    }
+
+    /// Return true if the score is a mate score, for either side.
+    #[inline(always)]
+    pub fn is_mate(self) -> bool {
+        self.0.abs() > Self::MAX_EVAL
+    }
+
+    /// Return the number of plies until checkmate if this is a mate score, negative if the side
+    /// to move is getting mated, or `None` for a normal evaluation.
+    #[inline(always)]
+    pub fn mate_plies(self) -> Option<i32> {
+        self.is_mate()
+            .then(|| self.0.signum() * (Self::MATE.0 - self.0.abs()))
+    }
+}
+
+/// Negate the [Score], to see it from the opponent's point of view.
+impl core::ops::Neg for Score {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+/// Add two [Score]s together, saturating at [Score::MATE] in both directions. The sum of two
+/// normal evaluations stays a normal evaluation, it never turns into a mate score.
+impl core::ops::Add for Score {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        let bound = if self.is_mate() || rhs.is_mate() {
+            Self::MATE.0
+        } else {
+            Self::MAX_EVAL
+        };
+        Self(self.0.saturating_add(rhs.0).clamp(-bound, bound))
+    }
+}
+
+/// Add two [Score]s together, saturating at [Score::MATE] in both directions.
+impl core::ops::AddAssign for Score {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Subtract two [Score]s, saturating at [Score::MATE] in both directions.
+impl core::ops::Sub for Score {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+/// Subtract two [Score]s, saturating at [Score::MATE] in both directions.
+impl core::ops::SubAssign for Score {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mate_in() {
+        let mate = Score::mate_in(3);
+        assert!(mate.is_mate());
+        assert_eq!(mate.mate_plies(), Some(3));
+        assert!(mate < Score::mate_in(1));
+        assert!(mate > Score::centipawns(i32::MAX));
+        assert!(mate > Score::centipawns(30000));
+        assert!(Score::MATE.is_mate());
+    }
+
+    #[test]
+    fn mated_in() {
+        let mated = Score::mated_in(2);
+        assert!(mated.is_mate());
+        assert_eq!(mated.mate_plies(), Some(-2));
+        assert!(mated < Score::centipawns(i32::MIN));
+        assert!(mated < Score::mated_in(4));
+        assert_eq!(-mated, Score::mate_in(2));
+    }
+
+    #[test]
+    fn centipawns() {
+        assert!(!Score::centipawns(150).is_mate());
+        assert_eq!(Score::centipawns(150).mate_plies(), None);
+        assert_eq!(Score::centipawns(150).value(), 150);
+        assert!(!Score::centipawns(i32::MAX).is_mate());
+        assert!(!Score::centipawns(i32::MIN).is_mate());
+        assert_eq!(Score::default(), Score::ZERO);
+    }
+
+    #[test]
+    fn saturating_add() {
+        assert_eq!(
+            Score::centipawns(100) + Score::centipawns(-250),
+            Score::centipawns(-150)
+        );
+        assert_eq!(Score::MATE + Score::centipawns(100), Score::MATE);
+        assert_eq!(-Score::MATE - Score::centipawns(100), -Score::MATE);
+        assert_eq!(Score::MATE + Score::MATE, Score::MATE);
+        assert_eq!(
+            Score::mate_in(3) + Score::centipawns(-100),
+            Score(Score::MATE.0 - 103)
+        );
+        let mut score = Score::ZERO;
+        score += Score::centipawns(30);
+        score -= Score::centipawns(10);
+        assert_eq!(score, Score::centipawns(20));
+    }
+
+    #[test]
+    fn add_evaluations_stays_evaluation() {
+        let sum = Score::centipawns(31000) + Score::centipawns(500);
+        assert!(!sum.is_mate());
+        assert_eq!(sum.mate_plies(), None);
+        assert_eq!(sum, Score::centipawns(i32::MAX));
+        let difference = Score::centipawns(-31000) - Score::centipawns(500);
+        assert!(!difference.is_mate());
+        assert_eq!(difference, Score::centipawns(i32::MIN));
+    }
+}
@@ -1,5 +1,11 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
 use crate::board::{
-    CastleRights, ChessBoard, ChessBoardBuilder, Color, File, Piece, Rank, Square, ValidationError,
+    CastleRights, CastlingFiles, ChessBoard, ChessBoardBuilder, Color, File, Piece, Rank, Square,
+    ValidationError,
 };
 
 /// A trait to mark items that can be converted from a FEN input.
@@ -18,8 +24,8 @@ pub enum FenError {
     InvalidPosition(ValidationError),
 }
 
-impl std::fmt::Display for FenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidFen => write!(f, "invalid FEN input"),
             Self::InvalidPosition(err) => write!(f, "invalid chess position: {}", err),
@@ -27,7 +33,14 @@ impl std::fmt::Display for FenError {
     }
 }
 
-impl std::error::Error for FenError {}
+impl core::error::Error for FenError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidPosition(err) => Some(err),
+            Self::InvalidFen => None,
+        }
+    }
+}
 
 /// Allow converting a [ValidationError] into [FenError], for use with the '?' operator.
 impl From<ValidationError> for FenError {
@@ -36,7 +49,105 @@ impl From<ValidationError> for FenError {
     }
 }
 
-/// Convert the castling rights segment of a FEN string to an array of [CastleRights].
+/// A castling side, as written in the castling rights segment of a FEN string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CastlingSide {
+    /// An X-FEN king-side letter (`K`/`k`), naming the outermost rook on that side of the king.
+    KingSide,
+    /// An X-FEN queen-side letter (`Q`/`q`), naming the outermost rook on that side of the king.
+    QueenSide,
+    /// A Shredder-FEN rook file (e.g: `H`/`b`), which gives its side relative to the king.
+    RookFile(File),
+}
+
+impl CastlingSide {
+    /// Parse a single castling character, return its [Color] and [CastlingSide].
+    fn from_char(c: char) -> Result<(Color, Self), FenError> {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let side = match c.to_ascii_lowercase() {
+            'k' => Self::KingSide,
+            'q' => Self::QueenSide,
+            file @ 'a'..='h' => Self::RookFile(File::from_index((file as u8 - b'a') as usize)),
+            _ => return Err(FenError::InvalidFen),
+        };
+        Ok((color, side))
+    }
+
+    /// Is the rook on the king side, given the [File] of the king. Return `Err(InvalidFen)` if
+    /// it names a rook on the king's file.
+    fn is_king_side(self, king: File) -> Result<bool, FenError> {
+        match self {
+            Self::KingSide => Ok(true),
+            Self::QueenSide => Ok(false),
+            Self::RookFile(file) if file == king => Err(FenError::InvalidFen),
+            Self::RookFile(file) => Ok(file > king),
+        }
+    }
+}
+
+/// Add the given side to the [CastleRights]. Return `Err(InvalidFen)` if it was already allowed,
+/// e.g: `"KK"` or `"KH"`.
+fn with_castling_side(rights: CastleRights, king_side: bool) -> Result<CastleRights, FenError> {
+    let (side, res) = if king_side {
+        (CastleRights::KingSide, rights.with_king_side())
+    } else {
+        (CastleRights::QueenSide, rights.with_queen_side())
+    };
+    if rights.remove(side) != rights {
+        return Err(FenError::InvalidFen);
+    }
+    Ok(res)
+}
+
+/// Return the file character of a castling rook in a Shredder-FEN string, e.g: `'H'` for white
+/// or `'b'` for black.
+fn castling_file_char(file: File, color: Color) -> char {
+    let c = (b'a' + file.index() as u8) as char;
+    match color {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+/// Convert the castling rights of a single color in a FEN string to [CastleRights], e.g: `"KQ"` or
+/// `"kq"`, or `"-"` if the player cannot castle. Mixing upper and lower case is not allowed.
+/// Shredder-FEN rook files (e.g: `"HA"` or `"hb"`) are also accepted, relative to the king on
+/// [File::E], though the file of the rook is not kept, see [CastlingFiles]. Naming the same side
+/// twice is not allowed.
+impl FromFen for CastleRights {
+    type Err = FenError;
+
+    fn from_fen(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > 2 {
+            return Err(FenError::InvalidFen);
+        }
+
+        if s == "-" {
+            return Ok(CastleRights::NoSide);
+        }
+
+        let is_white = s.starts_with(|c: char| c.is_uppercase());
+        let mut res = CastleRights::NoSide;
+        for b in s.chars() {
+            if b.is_uppercase() != is_white {
+                return Err(FenError::InvalidFen);
+            }
+            let (_, side) = CastlingSide::from_char(b)?;
+            res = with_castling_side(res, side.is_king_side(File::E)?)?;
+        }
+
+        Ok(res)
+    }
+}
+
+/// Convert the castling rights segment of a FEN string to an array of [CastleRights]. Both X-FEN
+/// (e.g: `"KQkq"`) and Shredder-FEN (e.g: `"HAha"`) notations are accepted, relative to the kings
+/// on [File::E], though the file of the rooks is not kept, see [CastlingFiles]. Naming the same
+/// side twice is not allowed.
 impl FromFen for [CastleRights; Color::NUM_VARIANTS] {
     type Err = FenError;
 
@@ -52,34 +163,66 @@ impl FromFen for [CastleRights; Color::NUM_VARIANTS] {
         }
 
         for b in s.chars() {
-            let color = if b.is_uppercase() {
-                Color::White
-            } else {
-                Color::Black
-            };
+            let (color, side) = CastlingSide::from_char(b)?;
             let rights = &mut res[color.index()];
-            match b {
-                'k' | 'K' => *rights = rights.with_king_side(),
-                'q' | 'Q' => *rights = rights.with_queen_side(),
-                _ => return Err(FenError::InvalidFen),
-            }
+            *rights = with_castling_side(*rights, side.is_king_side(File::E)?)?;
         }
 
         Ok(res)
     }
 }
 
-/// Convert a side to move segment of a FEN string to a [Color].
+/// Convert an array of [CastleRights] to the castling rights segment of a FEN string, e.g:
+/// `"KQkq"`, or `"-"` if neither player can castle.
+pub fn castling_to_fen(rights: &[CastleRights; Color::NUM_VARIANTS]) -> String {
+    let res: String = Color::iter()
+        .map(|color| rights[color.index()].to_fen(color))
+        .collect();
+    if res.is_empty() {
+        "-".to_string()
+    } else {
+        res
+    }
+}
+
+/// Convert an array of [CastleRights], castling with the rooks on the given [CastlingFiles], to
+/// the castling rights segment of a Shredder-FEN string, naming the file of each castling rook,
+/// e.g: `"HAha"` or `"HBh"`, or `"-"` if neither player can castle.
+pub fn castling_to_shredder_fen(
+    rights: &[CastleRights; Color::NUM_VARIANTS],
+    files: &[CastlingFiles; Color::NUM_VARIANTS],
+) -> String {
+    let res: String = Color::iter()
+        .flat_map(|color| {
+            let rights = rights[color.index()];
+            let files = files[color.index()];
+            let sides = [
+                (rights.has_king_side(), files.king_side()),
+                (rights.has_queen_side(), files.queen_side()),
+            ];
+            sides
+                .into_iter()
+                .filter(|&(allowed, _)| allowed)
+                .map(move |(_, file)| castling_file_char(file, color))
+        })
+        .collect();
+    if res.is_empty() {
+        "-".to_string()
+    } else {
+        res
+    }
+}
+
+/// Convert a side to move segment of a FEN string to a [Color], ignoring case.
 impl FromFen for Color {
     type Err = FenError;
 
     fn from_fen(s: &str) -> Result<Self, Self::Err> {
-        let res = match s {
-            "w" => Color::White,
-            "b" => Color::Black,
-            _ => return Err(FenError::InvalidFen),
-        };
-        Ok(res)
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Color::from_char(c.to_ascii_lowercase()).ok_or(FenError::InvalidFen),
+            _ => Err(FenError::InvalidFen),
+        }
     }
 }
 
@@ -118,7 +261,8 @@ impl FromFen for Piece {
     }
 }
 
-/// Return a [ChessBoard] from the given FEN string.
+/// Return a [ChessBoard] from the given FEN string. The half-move clock and full-move counter
+/// fields can be omitted, in which case they default to `0` and `1` respectively.
 impl FromFen for ChessBoard {
     type Err = FenError;
 
@@ -129,16 +273,12 @@ impl FromFen for ChessBoard {
         let side_to_move = split.next().ok_or(FenError::InvalidFen)?;
         let castling_rights = split.next().ok_or(FenError::InvalidFen)?;
         let en_passant_square = split.next().ok_or(FenError::InvalidFen)?;
-        let half_move_clock = split.next().ok_or(FenError::InvalidFen)?;
-        let full_move_counter = split.next().ok_or(FenError::InvalidFen)?;
+        // The clocks are commonly omitted, default to the start of a game
+        let half_move_clock = split.next().unwrap_or("0");
+        let full_move_counter = split.next().unwrap_or("1");
 
         let mut builder = ChessBoardBuilder::new();
 
-        let castle_rights = <[CastleRights; Color::NUM_VARIANTS]>::from_fen(castling_rights)?;
-        for color in Color::iter() {
-            builder.with_castle_rights(castle_rights[color.index()], color);
-        }
-
         builder.with_current_player(FromFen::from_fen(side_to_move)?);
 
         if let Some(square) = FromFen::from_fen(en_passant_square)? {
@@ -196,10 +336,246 @@ impl FromFen for ChessBoard {
             }
         };
 
+        // Parsed last, X-FEN castling rights depend on the position of the rooks
+        castling_from_fen(castling_rights, &mut builder)?;
+
         Ok(builder.try_into()?)
     }
 }
 
+/// Parse the castling rights segment of a FEN string, setting both the [CastleRights] and the
+/// [CastlingFiles] of each player in the given [ChessBoardBuilder], whose pieces must already be
+/// placed. X-FEN letters (`KQkq`) name the outermost rook on their side of the king, Shredder-FEN
+/// letters (e.g: `HAha`) name the file of the rook, and both can be mixed, e.g: `"KBkq"`. The king
+/// may stand on any [File] of its first rank, e.g: in Chess960 positions. Naming the same side
+/// twice, e.g: `"KKkq"` or `"KHkq"`, is not allowed.
+fn castling_from_fen(s: &str, builder: &mut ChessBoardBuilder) -> Result<(), FenError> {
+    if s.len() > 4 {
+        return Err(FenError::InvalidFen);
+    }
+
+    let mut rights = [CastleRights::NoSide; Color::NUM_VARIANTS];
+    let mut files = [CastlingFiles::STANDARD; Color::NUM_VARIANTS];
+
+    if s != "-" {
+        for b in s.chars() {
+            let (color, side) = CastlingSide::from_char(b)?;
+            let rank = color.first_rank();
+            let is_piece =
+                |file: File, piece| builder[Square::new(file, rank)] == Some((piece, color));
+            // A missing king defaults to its original square, for validation to reject it
+            let king = File::iter()
+                .find(|&file| is_piece(file, Piece::King))
+                .unwrap_or(File::E);
+            // Missing rooks default to the original corners, for validation to reject them
+            let file = match side {
+                CastlingSide::KingSide => File::iter()
+                    .filter(|&file| file > king && is_piece(file, Piece::Rook))
+                    .last()
+                    .unwrap_or(File::H),
+                CastlingSide::QueenSide => File::iter()
+                    .filter(|&file| file < king)
+                    .find(|&file| is_piece(file, Piece::Rook))
+                    .unwrap_or(File::A),
+                CastlingSide::RookFile(file) => file,
+            };
+
+            let index = color.index();
+            let king_side = side.is_king_side(king)?;
+            rights[index] = with_castling_side(rights[index], king_side)?;
+            files[index] = if king_side {
+                files[index].with_king_side(file)
+            } else {
+                files[index].with_queen_side(file)
+            }
+            .with_king(king);
+        }
+    }
+
+    for color in Color::iter() {
+        builder.with_castle_rights(rights[color.index()], color);
+        builder.with_castling_files(files[color.index()], color);
+    }
+
+    Ok(())
+}
+
+impl ChessBoard {
+    /// Return the FEN string of the position, with X-FEN castling rights: `KQkq` letters name the
+    /// outermost rook on either side of the king, any other rook is named by its file, e.g:
+    /// `"KBkq"`. The en-passant target is only written if it can be captured, as returned by
+    /// [ChessBoard::en_passant_fen] in strict mode. It can be parsed back with [FromFen::from_fen].
+    pub fn to_fen(&self) -> String {
+        self.fen_with_castling(&self.castling_fen())
+    }
+
+    /// Return the FEN string of the position, with Shredder-FEN castling rights naming the file
+    /// of each castling rook, e.g: `"HAha"`. The en-passant target is written as in
+    /// [ChessBoard::to_fen]. It can be parsed back with [FromFen::from_fen].
+    pub fn to_shredder_fen(&self) -> String {
+        let rights = [
+            self.castle_rights(Color::White),
+            self.castle_rights(Color::Black),
+        ];
+        let files = [
+            self.castling_files(Color::White),
+            self.castling_files(Color::Black),
+        ];
+        self.fen_with_castling(&castling_to_shredder_fen(&rights, &files))
+    }
+
+    /// Return the X-FEN castling rights segment of the position, see [ChessBoard::to_fen].
+    pub fn castling_fen(&self) -> String {
+        let mut res = String::new();
+        for color in Color::iter() {
+            let rights = self.castle_rights(color);
+            let files = self.castling_files(color);
+            let rooks = self.occupancy(Piece::Rook, color) & color.first_rank().into_bitboard();
+            let sides = [
+                (
+                    rights.has_king_side(),
+                    CastleRights::KingSide,
+                    files.king_side(),
+                ),
+                (
+                    rights.has_queen_side(),
+                    CastleRights::QueenSide,
+                    files.queen_side(),
+                ),
+            ];
+            for (allowed, side, file) in sides {
+                if !allowed {
+                    continue;
+                }
+                // Only the outermost rook on that side of the king can use the X-FEN letter
+                let is_outermost = rooks.into_iter().all(|square| match side {
+                    CastleRights::KingSide => square.file() <= file,
+                    _ => square.file() >= file,
+                });
+                if is_outermost {
+                    res.push_str(side.to_fen(color));
+                } else {
+                    res.push(castling_file_char(file, color));
+                }
+            }
+        }
+        if res.is_empty() {
+            "-".to_string()
+        } else {
+            res
+        }
+    }
+
+    /// Return the FEN string of the position, using the given castling rights segment.
+    fn fen_with_castling(&self, castling_rights: &str) -> String {
+        let pieces = self.piece_list();
+        let mut piece_placement = String::new();
+        for rank_index in (0..Rank::NUM_VARIANTS).rev() {
+            let rank = Rank::from_index(rank_index);
+            let mut empty = 0;
+            for file in File::iter() {
+                match pieces[Square::new(file, rank).index()] {
+                    None => empty += 1,
+                    Some((piece, color)) => {
+                        if empty != 0 {
+                            piece_placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        piece_placement.push(piece.to_fen(color));
+                    }
+                }
+            }
+            if empty != 0 {
+                piece_placement.push_str(&empty.to_string());
+            }
+            if rank != Rank::First {
+                piece_placement.push('/');
+            }
+        }
+
+        let en_passant_square = self.en_passant_fen(true).map_or("-".to_string(), |square| {
+            format!("{}", square).to_ascii_lowercase()
+        });
+
+        format!(
+            "{} {} {} {} {} {}",
+            piece_placement,
+            self.current_player().to_char(),
+            castling_rights,
+            en_passant_square,
+            self.half_move_clock(),
+            self.total_plies() / 2 + 1,
+        )
+    }
+
+    /// Parse a [ChessBoard] from a FEN string, like [FromFen::from_fen], but tolerate common
+    /// non-standard inputs found in datasets: missing side to move, castling rights, en-passant,
+    /// or clock fields, trailing fields, invalid clocks, and castling rights, en-passant square,
+    /// or half-move clock which do not match the position, which are discarded. The piece
+    /// placement must still be valid.
+    pub fn try_from_fen_relaxed(s: &str) -> Result<Self, FenError> {
+        let mut split = s.split_ascii_whitespace();
+
+        let piece_placement = split.next().ok_or(FenError::InvalidFen)?;
+        let side_to_move = split.next().unwrap_or("w");
+        let castling_rights = split.next().unwrap_or("-");
+        // Keep each valid castling right once, in the canonical order
+        let mut castling_rights: String = "KQkq"
+            .chars()
+            .filter(|&c| castling_rights.contains(c))
+            .collect();
+        let mut en_passant_square = split
+            .next()
+            .filter(|&s| Option::<Square>::from_fen(s).is_ok())
+            .unwrap_or("-");
+        let mut half_move_clock: u32 = split.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let full_move_counter: u32 = split
+            .next()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1)
+            .max(1);
+
+        let parse = |castling_rights: &str, en_passant_square: &str, half_move_clock: u32| {
+            let castling_rights = if castling_rights.is_empty() {
+                "-"
+            } else {
+                castling_rights
+            };
+            ChessBoard::from_fen(&format!(
+                "{piece_placement} {side_to_move} {castling_rights} {en_passant_square} \
+                 {half_move_clock} {full_move_counter}"
+            ))
+        };
+
+        loop {
+            match parse(&castling_rights, en_passant_square, half_move_clock) {
+                Err(FenError::InvalidPosition(ValidationError::InvalidCastlingRights)) => {
+                    // Only keep the castling rights which are valid on their own
+                    let valid_rights: String = castling_rights
+                        .chars()
+                        .filter(|&c| parse(&c.to_string(), "-", 0).is_ok())
+                        .collect();
+                    if valid_rights == castling_rights {
+                        return Err(ValidationError::InvalidCastlingRights.into());
+                    }
+                    castling_rights = valid_rights;
+                }
+                Err(FenError::InvalidPosition(ValidationError::InvalidEnPassant))
+                    if en_passant_square != "-" =>
+                {
+                    en_passant_square = "-";
+                }
+                Err(FenError::InvalidPosition(ValidationError::HalfMoveClockTooHigh))
+                    if half_move_clock != 0 =>
+                {
+                    half_move_clock = 0;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::board::Move;
@@ -216,25 +592,341 @@ mod test {
         );
     }
 
+    #[test]
+    fn side_to_move_case_and_whitespace() {
+        assert_eq!(
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR W KQkq - 0 1\n")
+                .unwrap(),
+            ChessBoard::default()
+        );
+        assert_eq!(
+            ChessBoard::from_fen("  rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR  B KQkq e3 0 1 ")
+                .unwrap(),
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .unwrap()
+        );
+        assert_eq!(Color::from_fen("x"), Err(FenError::InvalidFen));
+        assert_eq!(Color::from_fen("wb"), Err(FenError::InvalidFen));
+        assert_eq!(Color::from_fen(""), Err(FenError::InvalidFen));
+    }
+
+    #[test]
+    fn castle_rights_single_color() {
+        assert_eq!(CastleRights::from_fen("K"), Ok(CastleRights::KingSide));
+        assert_eq!(CastleRights::from_fen("q"), Ok(CastleRights::QueenSide));
+        assert_eq!(CastleRights::from_fen("KQ"), Ok(CastleRights::BothSides));
+        assert_eq!(CastleRights::from_fen("kq"), Ok(CastleRights::BothSides));
+        assert_eq!(CastleRights::from_fen("-"), Ok(CastleRights::NoSide));
+        assert_eq!(CastleRights::from_fen("HA"), Ok(CastleRights::BothSides));
+        assert_eq!(CastleRights::from_fen("a"), Ok(CastleRights::QueenSide));
+        assert_eq!(CastleRights::from_fen("GB"), Ok(CastleRights::BothSides));
+        for fen in ["", "Kq", "KQk", "x", "--", "E", "Ha"] {
+            assert_eq!(
+                CastleRights::from_fen(fen),
+                Err(FenError::InvalidFen),
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn castling_round_trip() {
+        assert_eq!(
+            castling_to_fen(&[CastleRights::BothSides, CastleRights::BothSides]),
+            "KQkq"
+        );
+        assert_eq!(
+            castling_to_fen(&[CastleRights::NoSide, CastleRights::NoSide]),
+            "-"
+        );
+        for white in CastleRights::iter() {
+            for black in CastleRights::iter() {
+                let rights = [white, black];
+                let fen = castling_to_fen(&rights);
+                assert_eq!(
+                    <[CastleRights; Color::NUM_VARIANTS]>::from_fen(&fen),
+                    Ok(rights)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn castling_shredder_fen() {
+        assert_eq!(
+            <[CastleRights; Color::NUM_VARIANTS]>::from_fen("HAha"),
+            Ok([CastleRights::BothSides, CastleRights::BothSides])
+        );
+        assert_eq!(
+            <[CastleRights; Color::NUM_VARIANTS]>::from_fen("Ha"),
+            Ok([CastleRights::KingSide, CastleRights::QueenSide])
+        );
+        // The side of the rook is given by its file
+        assert_eq!(
+            <[CastleRights; Color::NUM_VARIANTS]>::from_fen("Bg"),
+            Ok([CastleRights::QueenSide, CastleRights::KingSide])
+        );
+        assert_eq!(
+            <[CastleRights; Color::NUM_VARIANTS]>::from_fen("e"),
+            Err(FenError::InvalidFen)
+        );
+        let files = [CastlingFiles::STANDARD; Color::NUM_VARIANTS];
+        for white in CastleRights::iter() {
+            for black in CastleRights::iter() {
+                let rights = [white, black];
+                let fen = castling_to_shredder_fen(&rights, &files);
+                assert_eq!(
+                    <[CastleRights; Color::NUM_VARIANTS]>::from_fen(&fen),
+                    Ok(rights)
+                );
+            }
+        }
+        assert_eq!(
+            ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1"),
+            ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+        );
+        assert_eq!(
+            castling_to_shredder_fen(
+                &[CastleRights::BothSides, CastleRights::KingSide],
+                &[
+                    CastlingFiles::new(File::G, File::B),
+                    CastlingFiles::STANDARD
+                ]
+            ),
+            "GBh"
+        );
+    }
+
+    #[test]
+    fn shredder_fen_rook_on_b1() {
+        let fen = "r3k2r/8/8/8/8/8/8/1R2K2R w HBha - 0 1";
+        let position = ChessBoard::from_fen(fen).unwrap();
+        assert_eq!(
+            position.castling_files(Color::White),
+            CastlingFiles::new(File::H, File::B)
+        );
+        assert_eq!(
+            position.castling_files(Color::Black),
+            CastlingFiles::STANDARD
+        );
+        assert_eq!(position.to_shredder_fen(), fen);
+        // The rook on b1 is the outermost one, which X-FEN names with a letter
+        assert_eq!(position.to_fen(), "r3k2r/8/8/8/8/8/8/1R2K2R w KQkq - 0 1");
+        assert_eq!(ChessBoard::from_fen(&position.to_fen()), Ok(position));
+    }
+
+    #[test]
+    fn x_fen_inner_rook() {
+        let fen = "r3k2r/8/8/8/8/8/8/RR2K2R w KBkq - 0 1";
+        let position = ChessBoard::from_fen(fen).unwrap();
+        assert_eq!(
+            position.castling_files(Color::White),
+            CastlingFiles::new(File::H, File::B)
+        );
+        assert_eq!(position.to_fen(), fen);
+        assert_eq!(
+            position.to_shredder_fen(),
+            "r3k2r/8/8/8/8/8/8/RR2K2R w HBha - 0 1"
+        );
+        assert_eq!(
+            ChessBoard::from_fen(&position.to_shredder_fen()),
+            Ok(position.clone())
+        );
+
+        // X-FEN letters name the outermost rook
+        let outermost = ChessBoard::from_fen("r3k2r/8/8/8/8/8/8/RR2K2R w KQkq - 0 1").unwrap();
+        assert_eq!(
+            outermost.castling_files(Color::White),
+            CastlingFiles::STANDARD
+        );
+        assert_ne!(outermost, position);
+        assert_ne!(outermost.zobrist_hash(), position.zobrist_hash());
+    }
+
+    #[test]
+    fn castling_rooks_must_exist() {
+        for fen in [
+            "r3k2r/8/8/8/8/8/8/4K2R w KQkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R w KBkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQgq - 0 1",
+        ] {
+            assert_eq!(
+                ChessBoard::from_fen(fen),
+                Err(FenError::InvalidPosition(
+                    ValidationError::InvalidCastlingRights
+                )),
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn chess960_fen() {
+        let fen = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+        let position = ChessBoard::from_fen(fen).unwrap();
+        for color in Color::iter() {
+            assert_eq!(
+                position.castling_files(color),
+                CastlingFiles::new(File::H, File::F).with_king(File::G)
+            );
+        }
+        assert_eq!(position.to_shredder_fen(), fen);
+        assert_eq!(
+            position.to_fen(),
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9"
+        );
+        assert_eq!(ChessBoard::from_fen(&position.to_fen()), Ok(position));
+    }
+
+    #[test]
+    fn castling_duplicate_sides() {
+        for fen in [
+            "r3k2r/8/8/8/8/8/8/R3K2R w KKkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R w KHkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQaq - 0 1",
+            // A rook on the king's file is on neither side
+            "r3k2r/8/8/8/8/8/8/R3K2R w Kkqe - 0 1",
+            "4k3/8/8/8/8/8/8/5K1R w F - 0 1",
+        ] {
+            assert_eq!(
+                ChessBoard::from_fen(fen),
+                Err(FenError::InvalidFen),
+                "{}",
+                fen
+            );
+        }
+        assert_eq!(CastleRights::from_fen("KK"), Err(FenError::InvalidFen));
+        assert_eq!(CastleRights::from_fen("qa"), Err(FenError::InvalidFen));
+        assert_eq!(
+            <[CastleRights; Color::NUM_VARIANTS]>::from_fen("Kkh"),
+            Err(FenError::InvalidFen)
+        );
+    }
+
+    #[test]
+    fn to_fen() {
+        assert_eq!(
+            ChessBoard::default().to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        // The en-passant target is only written if it can be captured
+        assert_eq!(
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .unwrap()
+                .to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+        );
+        for fen in [
+            "r3k2r/8/8/8/3pP3/8/8/4K2R b Kkq e3 3 10",
+            "4k3/8/8/8/8/8/8/4K3 w - - 50 80",
+        ] {
+            assert_eq!(ChessBoard::from_fen(fen).unwrap().to_fen(), fen);
+        }
+        for position in ChessBoard::test_positions() {
+            assert_eq!(
+                ChessBoard::from_fen(&position.to_fen()),
+                Ok(position.normalized())
+            );
+            assert_eq!(
+                ChessBoard::from_fen(&position.to_shredder_fen()),
+                Ok(position.normalized())
+            );
+        }
+    }
+
+    #[test]
+    fn missing_clocks() {
+        assert_eq!(
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").unwrap(),
+            ChessBoard::default()
+        );
+        assert_eq!(
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3")
+                .unwrap(),
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .unwrap(),
+        );
+        assert_eq!(
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq"),
+            Err(FenError::InvalidFen)
+        );
+    }
+
+    #[test]
+    fn relaxed() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR W KQkq",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w kqQK",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KKQQkq e.p. 0 0",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 - - ;",
+        ] {
+            assert_eq!(
+                ChessBoard::try_from_fen_relaxed(fen),
+                Ok(ChessBoard::default()),
+                "{}",
+                fen
+            );
+        }
+
+        // Missing fields default to white to move, without castling rights
+        let no_castling =
+            ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1");
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w --",
+        ] {
+            assert_eq!(
+                ChessBoard::try_from_fen_relaxed(fen),
+                no_castling,
+                "{}",
+                fen
+            );
+        }
+
+        // Castling rights which do not match the board are discarded
+        assert_eq!(
+            ChessBoard::try_from_fen_relaxed("r3k3/8/8/8/8/8/8/4K2R w KQkq - 5 10"),
+            ChessBoard::from_fen("r3k3/8/8/8/8/8/8/4K2R w Kq - 5 10")
+        );
+        // As is a half-move clock which is too high
+        assert_eq!(
+            ChessBoard::try_from_fen_relaxed("4k3/8/8/8/8/8/8/4K3 w - - 50 1"),
+            ChessBoard::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1")
+        );
+
+        // The piece placement must be valid
+        for fen in [
+            "",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w",
+            "4k3/8/8/8/8/8/8/8 w",
+        ] {
+            assert!(ChessBoard::try_from_fen_relaxed(fen).is_err(), "{}", fen);
+        }
+    }
+
     #[test]
     fn en_passant() {
         // Start from default position
         let mut position = ChessBoard::default();
-        position.play_move_inplace(Move::new(Square::E2, Square::E4, None));
+        let _ = position.play_move_inplace(Move::new(Square::E2, Square::E4, None));
         assert_eq!(
             ChessBoard::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
                 .unwrap(),
             position
         );
         // And now c5
-        position.play_move_inplace(Move::new(Square::C7, Square::C5, None));
+        let _ = position.play_move_inplace(Move::new(Square::C7, Square::C5, None));
         assert_eq!(
             ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2")
                 .unwrap(),
             position
         );
         // Finally, Nf3
-        position.play_move_inplace(Move::new(Square::G1, Square::F3, None));
+        let _ = position.play_move_inplace(Move::new(Square::G1, Square::F3, None));
         assert_eq!(
             ChessBoard::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2 ")
                 .unwrap(),
@@ -1,4 +1,9 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod board;
+pub mod eval;
 pub mod fen;
 pub mod movegen;
 pub mod utils;
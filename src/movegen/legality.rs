@@ -0,0 +1,68 @@
+use crate::board::{ChessBoard, Move, Piece};
+
+/// Return true if playing the given pseudo-legal [Move] would leave the current player's king in
+/// check, e.g: moving a pinned piece, or moving the king to an attacked square. The board is not
+/// modified, nor cloned. Castling through an attacked square is *not* detected.
+/// The move must start from one of the current player's pieces, and it will panic otherwise.
+pub fn in_check_after(board: &ChessBoard, chess_move: Move) -> bool {
+    let color = board.current_player();
+    let (start, destination) = (chess_move.start(), chess_move.destination());
+    assert!(
+        board.color_occupancy(color).contains(start),
+        "the move must start from one of the current player's pieces"
+    );
+    // Unwrap is fine, we checked that the start square is occupied
+    let piece = board.piece_on(start).unwrap();
+
+    let mut captured = destination.into_bitboard();
+    if piece == Piece::Pawn && Some(destination) == board.en_passant() {
+        captured = color.backward_direction().move_board(captured);
+    }
+    let occupancy = (board.combined_occupancy() - start - captured) | destination;
+
+    let king = if piece == Piece::King {
+        destination
+    } else {
+        // Unwrap is fine, there should always be exactly one king per color
+        board.occupancy(Piece::King, color).try_into().unwrap()
+    };
+
+    let attackers = board.attackers_to(king, occupancy) & board.color_occupancy(!color);
+    !(attackers - captured).is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Square;
+    use crate::fen::FromFen;
+
+    #[test]
+    fn in_check_after() {
+        let position = ChessBoard::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+        // The bishop is pinned
+        assert!(super::in_check_after(
+            &position,
+            Move::new(Square::E2, Square::D3, None)
+        ));
+        // The king walks out of the pin
+        assert!(!super::in_check_after(
+            &position,
+            Move::new(Square::E1, Square::D1, None)
+        ));
+        // The king walks into an attack
+        assert!(super::in_check_after(
+            &ChessBoard::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap(),
+            Move::new(Square::E1, Square::F1, None)
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn in_check_after_opponent_piece() {
+        super::in_check_after(
+            &ChessBoard::default(),
+            Move::new(Square::E7, Square::E5, None),
+        );
+    }
+}
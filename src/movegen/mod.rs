@@ -1,9 +1,25 @@
 // Naive move generation
 mod naive;
 
+// Naive move generation, exposed as a reference implementation
+pub mod reference;
+
 // Magic bitboard generation
 mod wizardry;
+pub use wizardry::MagicMoves;
 
 // Magic bitboard definitions
 mod moves;
 pub use moves::*;
+
+// Legality checks
+mod legality;
+pub use legality::*;
+
+// Storage for generated moves
+mod move_list;
+pub use move_list::*;
+
+// Staged move generation for search
+mod staged;
+pub use staged::*;
@@ -0,0 +1,166 @@
+use crate::board::{Move, Square};
+
+/// An upper bound on the number of legal [Move]s in any position, and the number of [Move]s that
+/// a [MoveList] can hold. The highest number known to be reachable is 218.
+pub const MAX_MOVES: usize = 256;
+
+/// A list of [Move]s, stored inline to avoid allocating during move generation. It is large
+/// enough to hold all the moves that can be played from any position.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    /// Create an empty [MoveList].
+    pub fn new() -> Self {
+        Self {
+            moves: [Move::new(Square::A1, Square::A1, None); MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    /// Add a [Move] at the end of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is already full.
+    #[inline(always)]
+    pub fn push(&mut self, chess_move: Move) {
+        self.moves[self.len] = chess_move;
+        self.len += 1;
+    }
+
+    /// Remove all [Move]s from the list.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Access the [MoveList] as a slice of its current [Move]s.
+impl core::ops::Deref for MoveList {
+    type Target = [Move];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.moves[..self.len]
+    }
+}
+
+/// Access the [MoveList] as a slice of its current [Move]s. Allow mutations, e.g: sorting.
+impl core::ops::DerefMut for MoveList {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl core::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for MoveList {}
+
+/// Iterate over the [Move]s contained in the list.
+impl<'a> IntoIterator for &'a MoveList {
+    type IntoIter = core::slice::Iter<'a, Move>;
+    type Item = &'a Move;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::ChessBoard;
+    use crate::fen::FromFen;
+    use crate::utils::SimpleRng;
+
+    #[test]
+    fn max_moves() {
+        let position =
+            ChessBoard::from_fen("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1").unwrap();
+        assert_eq!(position.legal_moves().len(), 218);
+    }
+
+    #[test]
+    fn max_moves_random_games() {
+        let mut rng = SimpleRng::new();
+        let mut max_seen = 0;
+        for position in ChessBoard::test_positions() {
+            for _ in 0..20 {
+                let mut position = position.clone();
+                for _ in 0..100 {
+                    let moves = position.legal_moves();
+                    assert_eq!(moves.len(), position.count_legal_moves());
+                    max_seen = max_seen.max(moves.len());
+                    if moves.is_empty() {
+                        break;
+                    }
+                    let chess_move = moves[rng.gen() as usize % moves.len()];
+                    position = position.play_move(chess_move);
+                }
+            }
+        }
+        // No reachable position has more legal moves than the known maximum
+        assert!(max_seen <= 218);
+    }
+
+    #[test]
+    fn push() {
+        let mut moves = MoveList::new();
+        assert!(moves.is_empty());
+        moves.push(Move::new(Square::E2, Square::E4, None));
+        moves.push(Move::new(Square::G1, Square::F3, None));
+        assert_eq!(moves.len(), 2);
+        assert_eq!(
+            moves[..],
+            [
+                Move::new(Square::E2, Square::E4, None),
+                Move::new(Square::G1, Square::F3, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn clear() {
+        let mut moves = MoveList::new();
+        moves.push(Move::new(Square::E2, Square::E4, None));
+        moves.clear();
+        assert!(moves.is_empty());
+        assert_eq!(moves, MoveList::default());
+    }
+
+    #[test]
+    fn sort() {
+        let mut moves = MoveList::new();
+        moves.push(Move::new(Square::G1, Square::F3, None));
+        moves.push(Move::new(Square::E2, Square::E4, None));
+        moves.sort();
+        assert_eq!(
+            moves.iter().collect::<Vec<_>>(),
+            [
+                &Move::new(Square::E2, Square::E4, None),
+                &Move::new(Square::G1, Square::F3, None),
+            ]
+        );
+    }
+}
@@ -1,18 +1,16 @@
-use std::sync::OnceLock;
-
 use crate::{
-    board::{Bitboard, Color, File, Square},
+    board::{Bitboard, Color, Direction, File, Square},
     movegen::{
         naive,
         wizardry::{
             generate_bishop_magics, generate_rook_magics, MagicMoves, BISHOP_SEED, ROOK_SEED,
         },
     },
-    utils::RandGen,
+    utils::{OnceLock, RandGen},
 };
 
 // A pre-rolled RNG for magic bitboard generation, using pre-determined values.
-struct PreRolledRng {
+pub(super) struct PreRolledRng {
     numbers: [u64; Square::NUM_VARIANTS],
     current_index: usize,
 }
@@ -81,6 +79,19 @@ pub fn pawn_moves(color: Color, square: Square, blockers: Bitboard) -> Bitboard
     pawn_quiet_moves(color, square, blockers) | pawn_attacks(color, square)
 }
 
+/// Compute the set of squares reached by single pushes for a whole set of pawns of the given
+/// [Color], given the set of empty squares.
+pub fn pawn_single_pushes(color: Color, pawns: Bitboard, empty: Bitboard) -> Bitboard {
+    color.forward_direction().move_board(pawns) & empty
+}
+
+/// Compute the set of squares reached by double pushes for a whole set of pawns of the given
+/// [Color], given the set of empty squares. Only pawns on their starting rank are considered.
+pub fn pawn_double_pushes(color: Color, pawns: Bitboard, empty: Bitboard) -> Bitboard {
+    let single_pushes = pawn_single_pushes(color, pawns, empty);
+    pawn_single_pushes(color, single_pushes, empty) & color.fourth_rank().into_bitboard()
+}
+
 /// Compute the set of possible moves for a knight on a [Square].
 pub fn knight_moves(square: Square) -> Bitboard {
     static KNIGHT_MOVES: OnceLock<[Bitboard; Square::NUM_VARIANTS]> = OnceLock::new();
@@ -93,30 +104,91 @@ pub fn knight_moves(square: Square) -> Bitboard {
     })[square.index()]
 }
 
+/// Compute the set of squares attacked along a single ray starting from a [Square] in the given
+/// [Direction], stopping at (and including) the first square found in `blockers`.
+/// This does not make use of magic bitboards, and is meant for custom pin or x-ray logic.
+/// It does not make sense to use this function with knight-only directions, and it will panic in
+/// debug-mode if it happens.
+pub fn ray_attacks(square: Square, dir: Direction, blockers: Bitboard) -> Bitboard {
+    dir.slide_board_with_blockers(square.into_bitboard(), blockers)
+}
+
+static BISHOP_MAGICS: OnceLock<MagicMoves> = OnceLock::new();
+static ROOK_MAGICS: OnceLock<MagicMoves> = OnceLock::new();
+
+/// Return the lazily-initialized bishop [MagicMoves].
+fn bishop_magics() -> &'static MagicMoves {
+    BISHOP_MAGICS.get_or_init(|| {
+        let (magics, moves) = generate_bishop_magics(&mut PreRolledRng::new(BISHOP_SEED));
+        // SAFETY: we used the generator function to compute these values
+        unsafe { MagicMoves::new(magics, moves) }
+    })
+}
+
+/// Return the lazily-initialized rook [MagicMoves].
+fn rook_magics() -> &'static MagicMoves {
+    ROOK_MAGICS.get_or_init(|| {
+        let (magics, moves) = generate_rook_magics(&mut PreRolledRng::new(ROOK_SEED));
+        // SAFETY: we used the generator function to compute these values
+        unsafe { MagicMoves::new(magics, moves) }
+    })
+}
+
+/// Force the initialization of all move tables, instead of lazily computing them on first use.
+/// This must be called before using [bishop_moves_unchecked] or [rook_moves_unchecked].
+pub fn init_tables() {
+    let _ = pawn_quiet_moves(Color::White, Square::A2, Bitboard::EMPTY);
+    let _ = pawn_attacks(Color::White, Square::A2);
+    let _ = knight_moves(Square::A1);
+    let _ = king_moves(Square::A1);
+    let _ = bishop_magics();
+    let _ = rook_magics();
+}
+
+/// Return the memory used by the bishop and rook magic move tables, in bytes. This initializes
+/// the tables if they were not used yet.
+///
+/// The bishop table holds 5248 entries (~41KiB), the rook table holds 102400 entries (800KiB).
+/// With the `black-magic` feature, they hold 4866 and 102229 entries respectively.
+pub fn magic_tables_size() -> usize {
+    (bishop_magics().table_len() + rook_magics().table_len()) * core::mem::size_of::<Bitboard>()
+}
+
 /// Compute the set of possible moves for a bishop on a [Square], given its set of blockers.
 pub fn bishop_moves(square: Square, blockers: Bitboard) -> Bitboard {
-    static BISHOP_MAGICS: OnceLock<MagicMoves> = OnceLock::new();
-    BISHOP_MAGICS
-        .get_or_init(|| {
-            let (magics, moves) = generate_bishop_magics(&mut PreRolledRng::new(BISHOP_SEED));
-            // SAFETY: we used the generator function to compute these values
-            unsafe { MagicMoves::new(magics, moves) }
-        })
-        .query(square, blockers)
+    bishop_magics().query(square, blockers)
 }
 
 /// Compute the set of possible moves for a rook on a [Square], given its set of blockers.
 pub fn rook_moves(square: Square, blockers: Bitboard) -> Bitboard {
-    static ROOK_MAGICS: OnceLock<MagicMoves> = OnceLock::new();
-    ROOK_MAGICS
-        .get_or_init(|| {
-            let (magics, moves) = generate_rook_magics(&mut PreRolledRng::new(ROOK_SEED));
-            // SAFETY: we used the generator function to compute these values
-            unsafe { MagicMoves::new(magics, moves) }
-        })
+    rook_magics().query(square, blockers)
+}
+
+/// Compute the set of possible moves for a bishop on a [Square], given its set of blockers,
+/// without checking that the move tables were initialized.
+///
+/// # Safety
+///
+/// [init_tables] must have been called beforehand.
+#[inline(always)]
+pub unsafe fn bishop_moves_unchecked(square: Square, blockers: Bitboard) -> Bitboard {
+    BISHOP_MAGICS
+        .get()
+        .unwrap_unchecked()
         .query(square, blockers)
 }
 
+/// Compute the set of possible moves for a rook on a [Square], given its set of blockers,
+/// without checking that the move tables were initialized.
+///
+/// # Safety
+///
+/// [init_tables] must have been called beforehand.
+#[inline(always)]
+pub unsafe fn rook_moves_unchecked(square: Square, blockers: Bitboard) -> Bitboard {
+    ROOK_MAGICS.get().unwrap_unchecked().query(square, blockers)
+}
+
 /// Compute the set of possible moves for a queen on a [Square], given its set of blockers.
 pub fn queen_moves(square: Square, blockers: Bitboard) -> Bitboard {
     bishop_moves(square, blockers) | rook_moves(square, blockers)
@@ -134,6 +206,18 @@ pub fn king_moves(square: Square) -> Bitboard {
     })[square.index()]
 }
 
+/// Compute the set of squares within the given Chebyshev distance of a [Square], excluding the
+/// square itself. A `radius` of 1 is equivalent to [king_moves].
+pub fn surrounding(square: Square, radius: u8) -> Bitboard {
+    let mut res = square.into_bitboard();
+    for _ in 0..radius {
+        res = Direction::iter_royalty()
+            .map(|dir| dir.move_board(res))
+            .fold(res, |lhs, rhs| lhs | rhs);
+    }
+    res - square
+}
+
 /// Compute the squares which should be empty for a king-side castle of the given [Color].
 pub fn kind_side_castle_blockers(color: Color) -> Bitboard {
     let rank = color.first_rank();
@@ -145,3 +229,146 @@ pub fn queen_side_castle_blockers(color: Color) -> Bitboard {
     let rank = color.first_rank();
     Square::new(File::B, rank) | Square::new(File::C, rank) | Square::new(File::D, rank)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ray_attacks_match_bishop_moves() {
+        let blockers = Square::B2 | Square::C6 | Square::F6 | Square::G1;
+        for square in Square::iter() {
+            let rays = Direction::iter_bishop()
+                .map(|dir| ray_attacks(square, dir, blockers))
+                .fold(Bitboard::EMPTY, |lhs, rhs| lhs | rhs);
+            assert_eq!(rays, bishop_moves(square, blockers));
+        }
+    }
+
+    #[test]
+    fn queen_moves_are_bishop_and_rook_moves() {
+        for blockers in [
+            Bitboard::EMPTY,
+            Bitboard::ALL,
+            Square::B2 | Square::C6 | Square::F6 | Square::G1 | Square::D4,
+            Bitboard::RANKS[3] | Bitboard::FILES[5],
+        ] {
+            for square in Square::iter() {
+                assert_eq!(
+                    crate::movegen::queen_moves(square, blockers),
+                    bishop_moves(square, blockers) | rook_moves(square, blockers)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "black-magic"))]
+    fn magic_tables_size() {
+        assert_eq!(bishop_magics().table_len(), 5248);
+        assert_eq!(rook_magics().table_len(), 102400);
+        assert_eq!(super::magic_tables_size(), (5248 + 102400) * 8);
+    }
+
+    #[test]
+    #[cfg(feature = "black-magic")]
+    fn magic_tables_size() {
+        assert_eq!(bishop_magics().table_len(), 4866);
+        assert_eq!(rook_magics().table_len(), 102229);
+        assert_eq!(super::magic_tables_size(), (4866 + 102229) * 8);
+    }
+
+    #[test]
+    #[cfg(feature = "black-magic")]
+    fn black_magic_moves() {
+        let mut rng = crate::utils::SimpleRng::new();
+        for square in Square::iter() {
+            for _ in 0..256 {
+                let blockers = Bitboard(rng.gen() & rng.gen());
+                assert_eq!(
+                    rook_moves(square, blockers),
+                    naive::rook_moves(square, blockers),
+                    "{:?} {:?}",
+                    square,
+                    blockers
+                );
+                assert_eq!(
+                    bishop_moves(square, blockers),
+                    naive::bishop_moves(square, blockers),
+                    "{:?} {:?}",
+                    square,
+                    blockers
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unchecked_moves() {
+        init_tables();
+        let blockers = Square::B2 | Square::C6 | Square::F6 | Square::G1 | Square::D4;
+        for square in Square::iter() {
+            // SAFETY: the tables were initialized above
+            unsafe {
+                assert_eq!(
+                    bishop_moves_unchecked(square, blockers),
+                    bishop_moves(square, blockers)
+                );
+                assert_eq!(
+                    rook_moves_unchecked(square, blockers),
+                    rook_moves(square, blockers)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pawn_pushes() {
+        let position = crate::board::ChessBoard::default();
+        let empty = !position.combined_occupancy();
+        for color in Color::iter() {
+            let pawns = position.occupancy(crate::board::Piece::Pawn, color);
+            let single = pawn_single_pushes(color, pawns, empty);
+            let double = pawn_double_pushes(color, pawns, empty);
+            assert_eq!(single.count(), 8);
+            assert_eq!(single, color.third_rank().into_bitboard());
+            assert_eq!(double.count(), 8);
+            assert_eq!(double, color.fourth_rank().into_bitboard());
+        }
+
+        // Blocked pawns, and pawns off their starting rank
+        let pawns = Square::A2 | Square::B2 | Square::C3 | Square::D2;
+        let empty = !(pawns | Square::B3 | Square::D4);
+        assert_eq!(
+            pawn_single_pushes(Color::White, pawns, empty),
+            Square::A3 | Square::C4 | Square::D3
+        );
+        assert_eq!(
+            pawn_double_pushes(Color::White, pawns, empty),
+            Square::A4.into_bitboard()
+        );
+    }
+
+    #[test]
+    fn surrounding() {
+        for square in Square::iter() {
+            assert_eq!(super::surrounding(square, 0), Bitboard::EMPTY);
+            assert_eq!(super::surrounding(square, 1), king_moves(square));
+            assert_eq!(super::surrounding(square, 7), !square);
+        }
+        assert_eq!(super::surrounding(Square::D4, 2).count(), 24);
+        assert_eq!(super::surrounding(Square::A1, 2).count(), 8);
+    }
+
+    #[test]
+    fn ray_attacks_stop_at_blocker() {
+        assert_eq!(
+            ray_attacks(Square::A1, Direction::North, Square::A3.into_bitboard()),
+            Square::A2 | Square::A3
+        );
+        assert_eq!(
+            ray_attacks(Square::A1, Direction::South, Bitboard::EMPTY),
+            Bitboard::EMPTY
+        );
+    }
+}
@@ -10,5 +10,8 @@ pub use knight::*;
 pub mod pawn;
 pub use pawn::*;
 
+pub mod queen;
+pub use queen::*;
+
 pub mod rook;
 pub use rook::*;
@@ -23,11 +23,9 @@ pub fn pawn_moves(color: Color, square: Square, blockers: Bitboard) -> Bitboard
 }
 
 /// Computes the set of squares a pawn can capture, given its color.
+/// Pawns cannot stand on the first or last rank, but the result is still computed for them: it is
+/// used in reverse to find the pawns attacking a given square, e.g: a king on its first rank.
 pub fn pawn_captures(color: Color, square: Square) -> Bitboard {
-    if (square.rank() == Rank::First) || (square.rank() == Rank::Eighth) {
-        return Bitboard::EMPTY;
-    }
-
     let dir = color.forward_direction();
 
     let advanced = dir.move_board(square.into_bitboard());
@@ -108,6 +106,17 @@ mod test {
             pawn_captures(Color::Black, Square::B7),
             Square::A6 | Square::C6
         );
+        // Used in reverse, to find attackers of squares on the first and last ranks
+        assert_eq!(
+            pawn_captures(Color::Black, Square::E8),
+            Square::D7 | Square::F7
+        );
+        assert_eq!(
+            pawn_captures(Color::White, Square::E1),
+            Square::D2 | Square::F2
+        );
+        assert_eq!(pawn_captures(Color::White, Square::E8), Bitboard::EMPTY);
+        assert_eq!(pawn_captures(Color::Black, Square::E1), Bitboard::EMPTY);
         assert_eq!(
             pawn_captures(Color::Black, Square::H7),
             Square::G6.into_bitboard()
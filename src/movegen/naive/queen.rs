@@ -0,0 +1,29 @@
+use crate::board::{Bitboard, Direction, Square};
+
+/// Compute a queen's movement given a set of blockers that cannot be moved past.
+pub fn queen_moves(square: Square, blockers: Bitboard) -> Bitboard {
+    Direction::iter_royalty()
+        .map(|dir| dir.slide_board_with_blockers(square.into_bitboard(), blockers))
+        .fold(Bitboard::EMPTY, |lhs, rhs| lhs | rhs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::movegen::naive::{bishop_moves, rook_moves};
+
+    #[test]
+    fn queen_is_bishop_and_rook() {
+        let blockers = Square::B2 | Square::C6 | Square::F6 | Square::G1 | Square::D4;
+        for square in Square::iter() {
+            assert_eq!(
+                queen_moves(square, blockers),
+                bishop_moves(square, blockers) | rook_moves(square, blockers)
+            );
+        }
+        assert_eq!(
+            queen_moves(Square::A1, Bitboard::EMPTY),
+            (Bitboard::FILES[0] | Bitboard::RANKS[0] | Bitboard::DIAGONAL) - Square::A1
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Reference move generation, computed naively by walking the board. It is much slower than the
+//! table-based functions of [crate::movegen], but simple enough to be trusted when cross-checking
+//! them, e.g: in property tests.
+
+pub use super::naive::{
+    bishop_moves, king_moves, knight_moves, pawn_captures, pawn_moves, queen_moves, rook_moves,
+};
+
+#[cfg(test)]
+mod test {
+    use crate::board::{Bitboard, Square};
+    use crate::movegen;
+    use crate::utils::SimpleRng;
+
+    #[test]
+    fn sliders_agree_with_magic() {
+        let mut rng = SimpleRng::new();
+        for square in Square::iter() {
+            for _ in 0..100 {
+                // Sparse blockers are more likely to reach the edges of the board
+                let blockers = Bitboard::from_bits(rng.gen() & rng.gen()) - square;
+                assert_eq!(
+                    super::bishop_moves(square, blockers),
+                    movegen::bishop_moves(square, blockers)
+                );
+                assert_eq!(
+                    super::rook_moves(square, blockers),
+                    movegen::rook_moves(square, blockers)
+                );
+                assert_eq!(
+                    super::queen_moves(square, blockers),
+                    movegen::queen_moves(square, blockers)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn leapers_agree_with_tables() {
+        for square in Square::iter() {
+            assert_eq!(super::king_moves(square), movegen::king_moves(square));
+            assert_eq!(super::knight_moves(square), movegen::knight_moves(square));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::board::{Bitboard, ChessBoard, Move, Square};
+use crate::movegen::MoveList;
+
+/// The stages of a [MoveGenerator], in the order they are visited.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Stage {
+    TTMove,
+    GenerateCaptures,
+    Captures,
+    Killers(usize),
+    GenerateQuiets,
+    Quiets,
+    Done,
+}
+
+/// A staged move generator for search, yielding the legal [Move]s of a position lazily in the
+/// order in which they are most likely to cause a cut-off: the transposition table move first,
+/// then captures in Most-Valuable-Victim/Least-Valuable-Attacker order, the killer moves, and
+/// finally the remaining quiet moves. Each stage is only generated once the previous ones have
+/// been exhausted, and each legal move is yielded exactly once.
+#[derive(Clone)]
+pub struct MoveGenerator {
+    stage: Stage,
+    tt_move: Option<Move>,
+    killers: [Option<Move>; 2],
+    moves: MoveList,
+    index: usize,
+}
+
+impl MoveGenerator {
+    /// Create a [MoveGenerator] using the given transposition table move and killer moves, which
+    /// do not need to be legal in the position: illegal ones are skipped.
+    pub fn new(tt_move: Option<Move>, killers: [Option<Move>; 2]) -> Self {
+        Self {
+            stage: Stage::TTMove,
+            tt_move,
+            killers,
+            moves: MoveList::new(),
+            index: 0,
+        }
+    }
+
+    /// Return the next legal [Move] in the given position, or `None` once all have been yielded.
+    /// The same [ChessBoard] must be used for every call.
+    pub fn next(&mut self, board: &ChessBoard) -> Option<Move> {
+        loop {
+            match self.stage {
+                Stage::TTMove => {
+                    self.stage = Stage::GenerateCaptures;
+                    if let Some(chess_move) = self.tt_move.filter(|&mv| board.is_legal(mv)) {
+                        return Some(chess_move);
+                    }
+                }
+                Stage::GenerateCaptures => {
+                    let targets = board.color_occupancy(!board.current_player())
+                        | board
+                            .en_passant()
+                            .map_or(Bitboard::EMPTY, Square::into_bitboard);
+                    board.moves_to_into(targets, &mut self.moves);
+                    board.order_captures(&mut self.moves);
+                    self.stage = Stage::Captures;
+                }
+                Stage::Captures => {
+                    let Some(chess_move) = self.next_generated() else {
+                        self.stage = Stage::Killers(0);
+                        continue;
+                    };
+                    // Moves to the en-passant square are not all captures
+                    if board.captured_piece(chess_move).is_some() && !self.is_tt_move(chess_move) {
+                        return Some(chess_move);
+                    }
+                }
+                Stage::Killers(index) => {
+                    let Some(&killer) = self.killers.get(index) else {
+                        self.stage = Stage::GenerateQuiets;
+                        continue;
+                    };
+                    self.stage = Stage::Killers(index + 1);
+                    let Some(killer) = killer else {
+                        continue;
+                    };
+                    if self.is_tt_move(killer) || self.killers[..index].contains(&Some(killer)) {
+                        continue;
+                    }
+                    if board.captured_piece(killer).is_none() && board.is_legal(killer) {
+                        return Some(killer);
+                    }
+                }
+                Stage::GenerateQuiets => {
+                    self.moves.clear();
+                    self.index = 0;
+                    let targets = !board.color_occupancy(!board.current_player());
+                    board.moves_to_into(targets, &mut self.moves);
+                    self.stage = Stage::Quiets;
+                }
+                Stage::Quiets => {
+                    let Some(chess_move) = self.next_generated() else {
+                        self.stage = Stage::Done;
+                        continue;
+                    };
+                    // En-passant captures were already yielded
+                    if board.captured_piece(chess_move).is_none()
+                        && !self.is_tt_move(chess_move)
+                        && !self.killers.contains(&Some(chess_move))
+                    {
+                        return Some(chess_move);
+                    }
+                }
+                Stage::Done => return None,
+            }
+        }
+    }
+
+    /// Return the next [Move] of the current stage's list, if any.
+    fn next_generated(&mut self) -> Option<Move> {
+        let chess_move = self.moves.get(self.index).copied()?;
+        self.index += 1;
+        Some(chess_move)
+    }
+
+    /// Return true if the given [Move] is the transposition table move.
+    fn is_tt_move(&self, chess_move: Move) -> bool {
+        self.tt_move == Some(chess_move)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fen::FromFen;
+
+    fn collect(board: &ChessBoard, mut generator: MoveGenerator) -> Vec<Move> {
+        let mut res = Vec::new();
+        while let Some(chess_move) = generator.next(board) {
+            res.push(chess_move);
+        }
+        res
+    }
+
+    fn assert_legal_move_set(board: &ChessBoard, moves: &[Move]) {
+        let mut moves = moves.to_vec();
+        moves.sort();
+        let mut legal = board.legal_moves().to_vec();
+        legal.sort();
+        assert_eq!(moves, legal);
+    }
+
+    #[test]
+    fn yields_legal_moves() {
+        for board in [
+            ChessBoard::default(),
+            ChessBoard::kiwipete(),
+            ChessBoard::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap(),
+            ChessBoard::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap(),
+        ] {
+            let moves = collect(&board, MoveGenerator::new(None, [None, None]));
+            assert_legal_move_set(&board, &moves);
+        }
+    }
+
+    #[test]
+    fn stage_order() {
+        let board = ChessBoard::kiwipete();
+        let tt_move = Move::new(Square::E2, Square::A6, None);
+        let killer = Move::new(Square::A2, Square::A3, None);
+        // Not legal in this position
+        let illegal = Move::new(Square::E2, Square::E4, None);
+
+        let moves = collect(
+            &board,
+            MoveGenerator::new(Some(tt_move), [Some(illegal), Some(killer)]),
+        );
+        assert_legal_move_set(&board, &moves);
+
+        assert_eq!(moves[0], tt_move);
+        let captures = moves
+            .iter()
+            .skip(1)
+            .take_while(|&&chess_move| board.captured_piece(chess_move).is_some())
+            .count();
+        assert_eq!(moves[captures + 1], killer);
+        assert!(moves[captures + 2..]
+            .iter()
+            .all(|&chess_move| board.captured_piece(chess_move).is_none()));
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::{vec, vec::Vec};
+
 use crate::board::{Bitboard, Square};
 use crate::movegen::naive::{bishop_moves, rook_moves};
 use crate::utils::RandGen;
@@ -15,7 +17,8 @@ pub fn generate_rook_magics(rng: &mut dyn RandGen) -> MagicGenerationType {
     generate_magics(rng, generate_rook_mask, rook_moves)
 }
 
-fn generate_magics(
+/// Search for magic numbers for a piece, given its blockers mask and moves functions.
+pub fn generate_magics(
     rng: &mut dyn RandGen,
     mask_fn: impl Fn(Square) -> Bitboard,
     moves_fn: impl Fn(Square, Bitboard) -> Bitboard,
@@ -25,42 +28,111 @@ fn generate_magics(
 
     for square in Square::iter() {
         let mask = mask_fn(square);
+        assert!(!mask.is_empty(), "empty blockers mask for {:?}", square);
+        let occupancy_to_moves = occupancy_to_moves(square, mask, &moves_fn);
 
-        let occupancy_to_moves: Vec<_> = mask
-            .iter_power_set()
-            .map(|occupancy| (occupancy, moves_fn(square, occupancy)))
-            .collect();
-
-        'candidate_search: loop {
-            let mut candidate = Magic {
-                magic: magic_candidate(rng),
-                offset: 0,
-                mask,
-                shift: (64 - mask.count()) as u8,
-            };
-            let mut candidate_moves = vec![Bitboard::EMPTY; occupancy_to_moves.len()];
-
-            for (occupancy, moves) in occupancy_to_moves.iter().cloned() {
-                let index = candidate.get_index(occupancy);
-                // Non-constructive collision, try with another candidate
-                if !candidate_moves[index].is_empty() && candidate_moves[index] != moves {
-                    continue 'candidate_search;
-                }
-                candidate_moves[index] = moves;
+        let (mut magic, candidate_moves) = loop {
+            if let Some(found) = try_magic(magic_candidate(rng), mask, &occupancy_to_moves) {
+                break found;
             }
+        };
 
-            // We have filled all candidate boards, record the correct offset and add the moves
-            candidate.offset = boards.len();
-            magics.push(candidate);
-            boards.append(&mut candidate_moves);
-            break;
-        }
+        // We have filled all candidate boards, record the correct offset and add the moves
+        magic.offset = place_moves(&mut boards, candidate_moves);
+        magics.push(magic);
     }
 
+    // Unused entries are never queried
+    let boards = boards.into_iter().map(Option::unwrap_or_default).collect();
     (magics, boards)
 }
 
-fn magic_candidate(rng: &mut dyn RandGen) -> u64 {
+/// Compute the moves of a piece on a [Square] for every subset of its blockers `mask`.
+pub(super) fn occupancy_to_moves(
+    square: Square,
+    mask: Bitboard,
+    moves_fn: impl Fn(Square, Bitboard) -> Bitboard,
+) -> Vec<(Bitboard, Bitboard)> {
+    mask.iter_power_set()
+        .map(|occupancy| (occupancy, moves_fn(square, occupancy)))
+        .collect()
+}
+
+/// Check a magic number candidate, given the blockers mask and the moves for each of its subsets.
+/// Return the [Magic] (with a zero offset) and its moves table, or `None` if there was a
+/// non-constructive collision. Unused entries of the table are `None`, as a set of moves can be
+/// empty for some pieces.
+pub(super) fn try_magic(
+    magic: u64,
+    mask: Bitboard,
+    occupancy_to_moves: &[(Bitboard, Bitboard)],
+) -> Option<(Magic, Vec<Option<Bitboard>>)> {
+    let candidate = Magic {
+        magic,
+        offset: 0,
+        // Black magics set all the squares outside of the mask, see [Magic::get_index]
+        #[cfg(feature = "black-magic")]
+        mask: !mask,
+        #[cfg(not(feature = "black-magic"))]
+        mask,
+        shift: (64 - mask.count()) as u8,
+    };
+    let mut candidate_moves = vec![None; occupancy_to_moves.len()];
+
+    for &(occupancy, moves) in occupancy_to_moves {
+        let index = candidate.get_index(occupancy);
+        // Non-constructive collision, try with another candidate
+        if candidate_moves[index].is_some_and(|previous| previous != moves) {
+            return None;
+        }
+        candidate_moves[index] = Some(moves);
+    }
+
+    Some((candidate, candidate_moves))
+}
+
+/// Add the moves table of a [Square] at the end of the shared table, returning its offset.
+#[cfg(not(feature = "black-magic"))]
+fn place_moves(boards: &mut Vec<Option<Bitboard>>, mut moves: Vec<Option<Bitboard>>) -> usize {
+    let offset = boards.len();
+    boards.append(&mut moves);
+    offset
+}
+
+/// Add the moves table of a [Square] to the shared table, returning its offset. Black magics
+/// leave some entries of their table unused: trailing ones are not stored, and the table is
+/// overlapped with the end of the shared table as long as they agree on every used entry.
+#[cfg(feature = "black-magic")]
+fn place_moves(boards: &mut Vec<Option<Bitboard>>, mut moves: Vec<Option<Bitboard>>) -> usize {
+    while moves.last().is_some_and(Option::is_none) {
+        moves.pop();
+    }
+
+    let fits = |offset: usize| {
+        moves
+            .iter()
+            .zip(&boards[offset..])
+            .all(|(new, old)| new.is_none() || old.is_none() || new == old)
+    };
+    // Appending at the very end always fits
+    let offset = (boards.len().saturating_sub(moves.len())..=boards.len())
+        .find(|&offset| fits(offset))
+        .unwrap();
+
+    let end = offset + moves.len();
+    if boards.len() < end {
+        boards.resize(end, None);
+    }
+    for (board, moves) in boards[offset..].iter_mut().zip(moves) {
+        if moves.is_some() {
+            *board = moves;
+        }
+    }
+
+    offset
+}
+
+pub(super) fn magic_candidate(rng: &mut dyn RandGen) -> u64 {
     // Few bits makes for better candidates
     rng.gen() & rng.gen() & rng.gen()
 }
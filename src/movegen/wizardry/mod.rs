@@ -2,7 +2,10 @@ mod generation;
 pub(super) use generation::*;
 mod mask;
 
+use alloc::vec::Vec;
+
 use crate::board::{Bitboard, Square};
+use crate::utils::RandGen;
 
 /// A type representing the magic board indexing a given [crate::board::Square].
 #[derive(Clone, Debug)]
@@ -11,7 +14,8 @@ pub(super) struct Magic {
     pub(self) magic: u64,
     /// Base offset into the magic square table.
     pub(self) offset: usize,
-    /// Mask to apply to the blocker board before applying the magic.
+    /// Mask to apply to the blocker board before applying the magic. With the `black-magic`
+    /// feature, this is the complement of the relevant blockers.
     pub(self) mask: Bitboard,
     /// Length of the resulting mask after applying the magic.
     pub(self) shift: u8,
@@ -20,7 +24,11 @@ pub(super) struct Magic {
 impl Magic {
     /// Compute the index into the magics database for this set of `blockers`.
     pub fn get_index(&self, blockers: Bitboard) -> usize {
+        // "Fancy" magics only keep the relevant blockers, black magics set all the other squares
+        #[cfg(not(feature = "black-magic"))]
         let relevant_occupancy = (blockers & self.mask).0;
+        #[cfg(feature = "black-magic")]
+        let relevant_occupancy = (blockers | self.mask).0;
         let base_index = ((relevant_occupancy.wrapping_mul(self.magic)) >> self.shift) as usize;
         base_index + self.offset
     }
@@ -28,7 +36,7 @@ impl Magic {
 
 /// A type encapsulating a database of [Magic] bitboard moves.
 #[derive(Clone, Debug)]
-pub(crate) struct MagicMoves {
+pub struct MagicMoves {
     magics: Vec<Magic>,
     moves: Vec<Bitboard>,
 }
@@ -40,10 +48,35 @@ impl MagicMoves {
     /// # Safety
     ///
     /// This should only be called with values generated by [crate::movegen::wizardry::generation].
-    pub unsafe fn new(magics: Vec<Magic>, moves: Vec<Bitboard>) -> Self {
+    pub(super) unsafe fn new(magics: Vec<Magic>, moves: Vec<Bitboard>) -> Self {
         Self { magics, moves }
     }
 
+    /// Build a new [MagicMoves] for a custom sliding piece, given a function computing the
+    /// relevant blockers mask for each [Square], and a function computing its moves given a set
+    /// of blockers. The magic numbers are searched using the given [RandGen].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask_fn` returns an empty mask for any [Square].
+    pub fn build(
+        mask_fn: impl Fn(Square) -> Bitboard,
+        moves_fn: impl Fn(Square, Bitboard) -> Bitboard,
+        rng: &mut dyn RandGen,
+    ) -> Self {
+        let (magics, moves) = generate_magics(rng, mask_fn, moves_fn);
+        // SAFETY: we used the generator function to compute these values
+        unsafe { Self::new(magics, moves) }
+    }
+
+    /// Return the number of [Bitboard] entries in the moves table, shared by all squares. Each
+    /// square uses `2^n` entries, where `n` is the number of relevant blocker squares. With the
+    /// `black-magic` feature, unused entries at the end of each square's table are dropped, and
+    /// tables are overlapped where possible, making it smaller.
+    pub fn table_len(&self) -> usize {
+        self.moves.len()
+    }
+
     /// Get the set of valid moves for a piece standing on a [Square], given a set of blockers.
     pub fn query(&self, square: Square, blockers: Bitboard) -> Bitboard {
         // SAFETY: indices are in range by construction
@@ -59,6 +92,7 @@ impl MagicMoves {
 
 // region:sourcegen
 /// A set of magic numbers for bishop move generation.
+#[cfg(not(feature = "black-magic"))]
 pub(crate) const BISHOP_SEED: [u64; Square::NUM_VARIANTS] = [
     4634226011293351952,
     6918109887683821586,
@@ -127,6 +161,7 @@ pub(crate) const BISHOP_SEED: [u64; Square::NUM_VARIANTS] = [
 ];
 
 /// A set of magic numbers for rook move generation.
+#[cfg(not(feature = "black-magic"))]
 pub(crate) const ROOK_SEED: [u64; Square::NUM_VARIANTS] = [
     180144122814791812,
     10448386594766422036,
@@ -195,11 +230,153 @@ pub(crate) const ROOK_SEED: [u64; Square::NUM_VARIANTS] = [
 ];
 // endregion:sourcegen
 
+// region:sourcegen-black-magic
+/// A set of magic numbers for bishop move generation.
+#[cfg(feature = "black-magic")]
+pub(crate) const BISHOP_SEED: [u64; Square::NUM_VARIANTS] = [
+    9368059258907754498,
+    3605149132892668171,
+    16719655406242627664,
+    1153769229690905616,
+    2315279026731630608,
+    11538927328650463488,
+    5260349788209021440,
+    2918930864739977728,
+    4719776945311649808,
+    4612258323365896608,
+    2341946852282206226,
+    1152925357226328068,
+    1155174979495544256,
+    3476779608651747328,
+    13835058364587213400,
+    11241548465989419062,
+    2384656056917770304,
+    11530917419724310562,
+    3244843875187180545,
+    3461297857934688512,
+    144959647906791680,
+    441458489203622016,
+    10124514244873682953,
+    13695727950447599656,
+    1450181121861230668,
+    1166718314060464192,
+    216190374334464513,
+    4507997678080160,
+    9944229805488619520,
+    4611902081055326281,
+    1153491052907241540,
+    4631180370330718224,
+    10538466028348772352,
+    2378745589782679592,
+    4755810088630354112,
+    36592298341564928,
+    1158551279288713472,
+    1733903450873858688,
+    2450117220634984832,
+    10448395529959310466,
+    2305895821440452672,
+    6160929792170418176,
+    288234783393318912,
+    226305898858939393,
+    864691421202875392,
+    432355346761122304,
+    2305915585855226880,
+    3026562480185803008,
+    4611687157718876194,
+    6989657076390102019,
+    1765411059055202840,
+    5764607619136274944,
+    10377419452716253198,
+    4755853983634997248,
+    11529542705403169408,
+    4611693167971435205,
+    3170535310337648716,
+    4612495261681059889,
+    4827858800693315586,
+    1152932499729424577,
+    4611688235234591104,
+    4760446647501856929,
+    4632092988642959428,
+    2377918216946091033,
+];
+
+/// A set of magic numbers for rook move generation.
+#[cfg(feature = "black-magic")]
+pub(crate) const ROOK_SEED: [u64; Square::NUM_VARIANTS] = [
+    180144122814791812,
+    162132069344878593,
+    9367496304492568704,
+    36051337275770880,
+    36031545932270593,
+    144120694224978952,
+    2341986705231317248,
+    14555638532229628420,
+    10592607114752999424,
+    9223442543041052689,
+    1153062310856663040,
+    288371319806953472,
+    54606214353780737,
+    90916454186156033,
+    32088155936522276,
+    864831878828545152,
+    74345127983710336,
+    4701758148682711048,
+    19160090732929152,
+    36311371775746568,
+    1408474529399826,
+    145100900417930241,
+    4398452441392,
+    9223383032247895049,
+    108508743108476948,
+    4503877727068160,
+    2022192103288733714,
+    4702470500953096323,
+    1441437755929788546,
+    186336443172922512,
+    1154610380237440009,
+    3377880109252868,
+    144124019334971524,
+    9007474136846344,
+    11031657062592,
+    580120517038575616,
+    1180101436357216256,
+    1153207379785941504,
+    4639270567105529600,
+    297238126336672769,
+    10466365577904553984,
+    36047540793454596,
+    156218887489257488,
+    144151472228204553,
+    162131785900228616,
+    13853358326831710216,
+    1729382807221846020,
+    2594112986079690753,
+    2882321360147779104,
+    90071996851181824,
+    5836678388527407616,
+    11421130075605570048,
+    18313474264270336,
+    594523529341306624,
+    2306194855250903360,
+    92323929804785984,
+    9078667636376661,
+    4684306607520113170,
+    27021872692464657,
+    40532399735709713,
+    9008298900607107,
+    9007577770762786,
+    1170936186853138572,
+    4630263405582951434,
+];
+// endregion:sourcegen-black-magic
+
 #[cfg(test)]
 mod test {
-    use std::fmt::Write as _;
+    use core::fmt::Write as _;
 
     use super::*;
+    use crate::movegen::moves::PreRolledRng;
+    use crate::movegen::naive::{king_moves, rook_moves};
     use crate::utils::SimpleRng;
 
     fn split_twice<'a>(
@@ -212,8 +389,8 @@ mod test {
         Some((prefix, mid, suffix))
     }
 
-    fn array_string(piece_type: &str, values: &[Magic]) -> String {
-        let inner = || -> Result<String, std::fmt::Error> {
+    fn array_string(piece_type: &str, feature_cfg: &str, values: &[u64]) -> String {
+        let inner = || -> Result<String, core::fmt::Error> {
             let mut res = String::new();
 
             writeln!(
@@ -221,13 +398,14 @@ mod test {
                 "/// A set of magic numbers for {} move generation.",
                 piece_type
             )?;
+            writeln!(&mut res, "#[cfg({})]", feature_cfg)?;
             writeln!(
                 &mut res,
                 "pub(crate) const {}_SEED: [u64; Square::NUM_VARIANTS] = [",
                 piece_type.to_uppercase()
             )?;
             for magic in values {
-                writeln!(&mut res, "    {},", magic.magic)?;
+                writeln!(&mut res, "    {},", magic)?;
             }
             writeln!(&mut res, "];")?;
 
@@ -238,23 +416,109 @@ mod test {
     }
 
     #[test]
-    #[ignore = "slow"]
-    // Regenerates the magic bitboard numbers.
-    fn regen_magic_seeds() {
-        // We only care about the magics, the moves can be recomputed at runtime ~cheaply.
-        let (bishop_magics, _) = generate_bishop_magics(&mut SimpleRng::new());
-        let (rook_magics, _) = generate_rook_magics(&mut SimpleRng::new());
+    fn build_rook_table() {
+        let magic_moves = MagicMoves::build(
+            mask::generate_rook_mask,
+            rook_moves,
+            &mut PreRolledRng::new(ROOK_SEED),
+        );
+
+        let mut rng = SimpleRng::new();
+
+        for square in Square::iter() {
+            for _ in 0..32 {
+                let blockers = Bitboard(rng.gen() & rng.gen());
+                assert_eq!(
+                    magic_moves.query(square, blockers),
+                    crate::movegen::rook_moves(square, blockers),
+                    "{:?} {:?}",
+                    square,
+                    blockers
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn build_table_with_empty_moves() {
+        // A piece which can only move onto blockers, so it has no moves on an empty board
+        let magic_moves = MagicMoves::build(
+            mask::generate_rook_mask,
+            |_, blockers| blockers,
+            &mut SimpleRng::new(),
+        );
+
+        let mut rng = SimpleRng::new();
+
+        for square in Square::iter() {
+            let mask = mask::generate_rook_mask(square);
+            assert_eq!(magic_moves.query(square, Bitboard::EMPTY), Bitboard::EMPTY);
+            for _ in 0..32 {
+                let blockers = Bitboard(rng.gen() & rng.gen());
+                assert_eq!(
+                    magic_moves.query(square, blockers),
+                    blockers & mask,
+                    "{:?} {:?}",
+                    square,
+                    blockers
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn build_table_without_moves() {
+        let magic_moves =
+            MagicMoves::build(king_moves, |_, _| Bitboard::EMPTY, &mut SimpleRng::new());
+
+        for square in Square::iter() {
+            for blockers in king_moves(square).iter_power_set() {
+                assert_eq!(magic_moves.query(square, blockers), Bitboard::EMPTY);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn build_bishop_table_with_rand() {
+        use crate::movegen::naive::bishop_moves;
+        use rand::{rngs::StdRng, SeedableRng};
 
+        let magic_moves = MagicMoves::build(
+            mask::generate_bishop_mask,
+            bishop_moves,
+            &mut StdRng::seed_from_u64(42),
+        );
+
+        let mut rng = SimpleRng::new();
+
+        for square in Square::iter() {
+            for _ in 0..32 {
+                let blockers = Bitboard(rng.gen() & rng.gen());
+                assert_eq!(
+                    magic_moves.query(square, blockers),
+                    bishop_moves(square, blockers),
+                    "{:?} {:?}",
+                    square,
+                    blockers
+                );
+            }
+        }
+    }
+
+    /// Replace the given source generation region of this file with the bishop and rook magics,
+    /// panicking if it was not up-to-date.
+    fn regen_region(region: &str, feature_cfg: &str, bishop_magics: &[u64], rook_magics: &[u64]) {
         let original_text = std::fs::read_to_string(file!()).unwrap();
 
-        let bishop_array = array_string("bishop", &bishop_magics[..]);
-        let rook_array = array_string("rook", &rook_magics[..]);
+        let bishop_array = array_string("bishop", feature_cfg, bishop_magics);
+        let rook_array = array_string("rook", feature_cfg, rook_magics);
 
         let new_text = {
-            let start_marker = "// region:sourcegen\n";
-            let end_marker = "// endregion:sourcegen\n";
+            let start_marker = format!("// region:{region}\n");
+            let end_marker = format!("// endregion:{region}\n");
             let (prefix, _, suffix) =
-                split_twice(&original_text, start_marker, end_marker).unwrap();
+                split_twice(&original_text, &start_marker, &end_marker).unwrap();
             format!("{prefix}{start_marker}{bishop_array}\n{rook_array}{end_marker}{suffix}")
         };
 
@@ -263,4 +527,76 @@ mod test {
             panic!("source was not up-to-date")
         }
     }
+
+    #[test]
+    #[ignore = "slow"]
+    #[cfg(not(feature = "black-magic"))]
+    // Regenerates the magic bitboard numbers.
+    fn regen_magic_seeds() {
+        // We only care about the magics, the moves can be recomputed at runtime ~cheaply.
+        let (bishop_magics, _) = generate_bishop_magics(&mut SimpleRng::new());
+        let (rook_magics, _) = generate_rook_magics(&mut SimpleRng::new());
+        let magic_numbers = |magics: Vec<Magic>| magics.iter().map(|m| m.magic).collect::<Vec<_>>();
+
+        regen_region(
+            "sourcegen",
+            "not(feature = \"black-magic\")",
+            &magic_numbers(bishop_magics),
+            &magic_numbers(rook_magics),
+        );
+    }
+
+    /// Search for the black magic of each [Square] which uses the fewest entries of its table,
+    /// among `tries` candidates.
+    #[cfg(feature = "black-magic")]
+    fn search_black_magics(
+        rng: &mut dyn RandGen,
+        mask_fn: impl Fn(Square) -> Bitboard,
+        moves_fn: impl Fn(Square, Bitboard) -> Bitboard,
+        tries: usize,
+    ) -> Vec<u64> {
+        let used_len = |moves: &[Option<Bitboard>]| {
+            moves.iter().rposition(|moves| moves.is_some()).unwrap() + 1
+        };
+
+        Square::iter()
+            .map(|square| {
+                let mask = mask_fn(square);
+                let occupancy_to_moves = occupancy_to_moves(square, mask, &moves_fn);
+                (0..tries)
+                    .filter_map(|_| try_magic(magic_candidate(rng), mask, &occupancy_to_moves))
+                    .min_by_key(|(_, moves)| used_len(moves))
+                    .unwrap()
+                    .0
+                    .magic
+            })
+            .collect()
+    }
+
+    #[test]
+    #[ignore = "slow, run it in release mode"]
+    #[cfg(feature = "black-magic")]
+    // Regenerates the black magic bitboard numbers.
+    fn regen_black_magic_seeds() {
+        const TRIES: usize = 1 << 22;
+        let bishop_magics = search_black_magics(
+            &mut SimpleRng::new(),
+            mask::generate_bishop_mask,
+            crate::movegen::naive::bishop_moves,
+            TRIES,
+        );
+        let rook_magics = search_black_magics(
+            &mut SimpleRng::new(),
+            mask::generate_rook_mask,
+            rook_moves,
+            TRIES,
+        );
+
+        regen_region(
+            "sourcegen-black-magic",
+            "feature = \"black-magic\"",
+            &bishop_magics,
+            &rook_magics,
+        );
+    }
 }
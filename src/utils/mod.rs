@@ -1,5 +1,8 @@
-pub(crate) mod rand;
-pub(crate) use rand::*;
+pub(crate) mod once;
+pub(crate) use once::*;
+
+pub mod rand;
+pub use rand::*;
 
 pub mod static_assert;
 pub use static_assert::*;
@@ -0,0 +1,98 @@
+/// A cell which can be written to only once, used for lazily-initialized tables.
+#[cfg(feature = "std")]
+pub(crate) use std::sync::OnceLock;
+
+/// A cell which can be written to only once, used for lazily-initialized tables. This is a
+/// minimal spin-lock based replacement for `std::sync::OnceLock`, when `std` is not available.
+#[cfg(not(feature = "std"))]
+pub(crate) use spin::OnceLock;
+
+#[cfg(not(feature = "std"))]
+mod spin {
+    use core::cell::UnsafeCell;
+    use core::mem::MaybeUninit;
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    const UNINIT: u8 = 0;
+    const RUNNING: u8 = 1;
+    const DONE: u8 = 2;
+
+    pub(crate) struct OnceLock<T> {
+        state: AtomicU8,
+        value: UnsafeCell<MaybeUninit<T>>,
+    }
+
+    // SAFETY: the value is only written once, before being shared, like `std::sync::OnceLock`
+    unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+    unsafe impl<T: Send> Send for OnceLock<T> {}
+
+    impl<T> OnceLock<T> {
+        pub const fn new() -> Self {
+            Self {
+                state: AtomicU8::new(UNINIT),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }
+
+        /// Return the value if it has been initialized.
+        #[inline(always)]
+        pub fn get(&self) -> Option<&T> {
+            if self.state.load(Ordering::Acquire) == DONE {
+                // SAFETY: the value has been initialized, and is never written to again
+                Some(unsafe { (*self.value.get()).assume_init_ref() })
+            } else {
+                None
+            }
+        }
+
+        /// Return the value, initializing it with `f` if needed. Concurrent callers spin until the
+        /// value is available. If `f` panics, any other caller will spin forever.
+        pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+            if let Some(value) = self.get() {
+                return value;
+            }
+
+            match self
+                .state
+                .compare_exchange(UNINIT, RUNNING, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    // SAFETY: we are the only ones allowed to write to the value
+                    unsafe { (*self.value.get()).write(f()) };
+                    self.state.store(DONE, Ordering::Release);
+                }
+                Err(_) => {
+                    while self.state.load(Ordering::Acquire) != DONE {
+                        core::hint::spin_loop();
+                    }
+                }
+            }
+
+            // SAFETY: the value has been initialized, and is never written to again
+            unsafe { (*self.value.get()).assume_init_ref() }
+        }
+    }
+
+    impl<T> Drop for OnceLock<T> {
+        fn drop(&mut self) {
+            if *self.state.get_mut() == DONE {
+                // SAFETY: the value has been initialized
+                unsafe { self.value.get_mut().assume_init_drop() };
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn get_or_init() {
+            let cell = OnceLock::new();
+            assert_eq!(cell.get(), None);
+            assert_eq!(cell.get_or_init(|| 42), &42);
+            assert_eq!(cell.get_or_init(|| 0), &42);
+            assert_eq!(cell.get(), Some(&42));
+        }
+    }
+}
@@ -4,10 +4,8 @@ pub trait RandGen {
 }
 
 // A simple pcg64_fast RNG implementation, for code-generation.
-#[cfg(test)]
 pub struct SimpleRng(u128);
 
-#[cfg(test)]
 impl SimpleRng {
     pub fn new() -> Self {
         Self(0xcafef00dd15ea5e5 | 1) // https://xkcd.com/221/
@@ -25,13 +23,26 @@ impl SimpleRng {
     }
 }
 
-#[cfg(test)]
+impl Default for SimpleRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RandGen for SimpleRng {
     fn gen(&mut self) -> u64 {
         self.gen()
     }
 }
 
+/// Use any [rand] RNG to generate values, e.g: for magic bitboard generation.
+#[cfg(feature = "rand")]
+impl<T: ::rand::RngCore + ?Sized> RandGen for T {
+    fn gen(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;